@@ -0,0 +1,148 @@
+#![no_main]
+
+use frost_ristretto255::{
+    keys::{self, IdentifierList, KeyPackage},
+    round1::{NonceCommitment, SigningCommitments},
+    round2::SignatureShare,
+    Identifier, Ristretto255Sha512,
+};
+use libfuzzer_sys::{arbitrary::Arbitrary, fuzz_target};
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use roast_core::{Error, SessionStatus};
+use std::collections::BTreeSet;
+
+type Coordinator = roast_core::Coordinator<Ristretto255Sha512>;
+type Signer = roast_core::Signer<Ristretto255Sha512>;
+
+#[derive(Debug, Arbitrary)]
+enum Reply {
+    /// Delivers reply of honest signer.
+    Honest(u8),
+    /// Delivers arbitrary reply.
+    Raw {
+        identifier: u16,
+        signature_share: Option<[u8; 32]>,
+        hiding: [u8; 32],
+        binding: [u8; 32],
+    },
+}
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    min_signers: u8,
+    extra_signers: u8,
+    seed: u64,
+    replies: Vec<Reply>,
+}
+
+fuzz_target!(|input: Input| {
+    let min_signers = 2 + u16::from(input.min_signers % 4);
+    let max_signers = min_signers + u16::from(input.extra_signers % 4);
+    let mut rng = ChaCha20Rng::seed_from_u64(input.seed);
+
+    let (secret_shares, public_key_package) =
+        keys::generate_with_dealer(max_signers, min_signers, IdentifierList::Default, &mut rng)
+            .unwrap();
+
+    let message = b"message to sign".to_vec();
+    let mut coordinator = Coordinator::new(
+        max_signers,
+        min_signers,
+        public_key_package.clone(),
+        message.clone(),
+    )
+    .unwrap();
+
+    let mut signers: Vec<(Identifier, Signer, Option<SignatureShare>)> = secret_shares
+        .into_iter()
+        .map(|(identifier, secret_share)| {
+            let key_package = KeyPackage::try_from(secret_share).unwrap();
+            (
+                identifier,
+                Signer::new(key_package, message.clone(), &mut rng).unwrap(),
+                None,
+            )
+        })
+        .collect();
+
+    for reply in input.replies {
+        let (identifier, signature_share, signing_commitments) = match reply {
+            Reply::Honest(index) => {
+                let (identifier, signer, signature_share) =
+                    &mut signers[index as usize % max_signers as usize];
+                (
+                    *identifier,
+                    signature_share.take(),
+                    signer.signing_commitments(),
+                )
+            }
+            Reply::Raw {
+                identifier,
+                signature_share,
+                hiding,
+                binding,
+            } => {
+                let Ok(identifier) = Identifier::try_from(identifier) else {
+                    continue;
+                };
+                let signature_share =
+                    match signature_share.map(|bytes| SignatureShare::deserialize(&bytes)) {
+                        Some(Ok(signature_share)) => Some(signature_share),
+                        Some(Err(_)) => continue,
+                        None => None,
+                    };
+                let (Ok(hiding), Ok(binding)) = (
+                    NonceCommitment::deserialize(&hiding),
+                    NonceCommitment::deserialize(&binding),
+                ) else {
+                    continue;
+                };
+                (
+                    identifier,
+                    signature_share,
+                    SigningCommitments::new(hiding, binding),
+                )
+            }
+        };
+
+        match coordinator.receive(identifier, signature_share, signing_commitments) {
+            Ok(SessionStatus::InProgress) => {}
+            Ok(SessionStatus::Started {
+                signers: session_signers,
+                signing_package,
+            }) => {
+                assert_eq!(session_signers.len(), min_signers as usize);
+
+                for (identifier, signer, signature_share) in &mut signers {
+                    if session_signers.contains(identifier) {
+                        *signature_share = signer.receive(&signing_package, &mut rng).ok();
+                    }
+                }
+            }
+            Ok(SessionStatus::Finished { signature }) => {
+                assert!(public_key_package
+                    .verifying_key()
+                    .verify(&message, &signature)
+                    .is_ok());
+                return;
+            }
+            Err(Error::TooManyMaliciousSigners) => return,
+            Err(_) => {}
+        }
+
+        assert!(coordinator.malicious_signers().len() <= (max_signers - min_signers) as usize);
+        assert!(coordinator.session_count() <= max_signers - min_signers + 1);
+
+        let mut waiting_signers = BTreeSet::new();
+        for session in coordinator.open_sessions() {
+            for identifier in session.missing_signers() {
+                assert!(waiting_signers.insert(identifier));
+            }
+        }
+
+        assert!(coordinator
+            .responsive_signers()
+            .iter()
+            .all(|identifier| !waiting_signers.contains(identifier)));
+    }
+});
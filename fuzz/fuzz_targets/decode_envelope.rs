@@ -0,0 +1,44 @@
+#![no_main]
+
+use frost_ristretto255::Ristretto255Sha512;
+use libfuzzer_sys::fuzz_target;
+
+type Envelope = roast_core::Envelope<Ristretto255Sha512>;
+type SignedEnvelope = roast_core::SignedEnvelope<Ristretto255Sha512>;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((selector, bytes)) = data.split_first() else {
+        return;
+    };
+
+    match selector % 4 {
+        0 => {
+            if let Ok(envelope) = Envelope::decode_compact(bytes) {
+                // Compact encoding is canonical.
+                assert_eq!(envelope.to_compact().unwrap(), bytes);
+                assert_eq!(envelope.compact_len(), Ok(bytes.len()));
+            }
+        }
+        1 => {
+            if let Ok(signed_envelope) = SignedEnvelope::decode_compact(bytes) {
+                assert_eq!(signed_envelope.to_compact().unwrap(), bytes);
+            }
+        }
+        2 => {
+            if let Ok(envelope) = Envelope::deserialize(bytes) {
+                assert_eq!(
+                    Envelope::deserialize(&envelope.serialize().unwrap()),
+                    Ok(envelope)
+                );
+            }
+        }
+        _ => {
+            if let Ok(envelope) = Envelope::decode_protobuf(bytes) {
+                assert_eq!(
+                    Envelope::decode_protobuf(&envelope.encode_protobuf().unwrap()),
+                    Ok(envelope)
+                );
+            }
+        }
+    }
+});
@@ -0,0 +1,75 @@
+#![no_main]
+
+use frost_ristretto255::{
+    keys::PublicKeyPackage, round1::SigningCommitments, round2::SignatureShare, Identifier,
+    Ristretto255Sha512, Signature, SigningPackage,
+};
+use libfuzzer_sys::fuzz_target;
+
+type Transcript = roast_core::Transcript<Ristretto255Sha512>;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((selector, bytes)) = data.split_first() else {
+        return;
+    };
+
+    match selector % 7 {
+        0 => {
+            if let Ok(identifier) = Identifier::deserialize(bytes) {
+                assert_eq!(
+                    Identifier::deserialize(&identifier.serialize()),
+                    Ok(identifier)
+                );
+            }
+        }
+        1 => {
+            if let Ok(signature_share) = SignatureShare::deserialize(bytes) {
+                assert_eq!(
+                    SignatureShare::deserialize(&signature_share.serialize()),
+                    Ok(signature_share)
+                );
+            }
+        }
+        2 => {
+            if let Ok(signature) = Signature::deserialize(bytes) {
+                assert_eq!(
+                    Signature::deserialize(&signature.serialize().unwrap()),
+                    Ok(signature)
+                );
+            }
+        }
+        3 => {
+            if let Ok(signing_commitments) = SigningCommitments::deserialize(bytes) {
+                assert_eq!(
+                    SigningCommitments::deserialize(&signing_commitments.serialize().unwrap()),
+                    Ok(signing_commitments)
+                );
+            }
+        }
+        4 => {
+            if let Ok(signing_package) = SigningPackage::deserialize(bytes) {
+                assert_eq!(
+                    SigningPackage::deserialize(&signing_package.serialize().unwrap()),
+                    Ok(signing_package)
+                );
+            }
+        }
+        5 => {
+            if let Ok(public_key_package) = PublicKeyPackage::deserialize(bytes) {
+                assert_eq!(
+                    PublicKeyPackage::deserialize(&public_key_package.serialize().unwrap()),
+                    Ok(public_key_package)
+                );
+            }
+        }
+        _ => {
+            if let Ok(transcript) = Transcript::deserialize(bytes) {
+                assert_eq!(
+                    Transcript::deserialize(&transcript.serialize().unwrap()),
+                    Ok(transcript.clone())
+                );
+                let _ = transcript.replay();
+            }
+        }
+    }
+});
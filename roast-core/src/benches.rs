@@ -0,0 +1,228 @@
+//! Benchmarks.
+
+use crate::{
+    frost::{
+        keys::{self, IdentifierList, KeyPackage, PublicKeyPackage},
+        round1::SigningCommitments,
+        round2::SignatureShare,
+        Ciphersuite, Field, Group, Identifier, SigningPackage,
+    },
+    Coordinator, Error, SessionStatus, Signer,
+};
+use alloc::{collections::BTreeMap, format, vec::Vec};
+use criterion::{BatchSize, BenchmarkId, Criterion, Throughput};
+use rand::{CryptoRng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+/// Committee sizes as `(min_signers, max_signers)`.
+const COMMITTEES: [(u16, u16); 4] = [(2, 3), (7, 10), (67, 100), (667, 1000)];
+
+/// Fractions of malicious signers in percent of `max_signers`.
+const MALICIOUS_PERCENTS: [u16; 3] = [0, 10, 33];
+
+const MESSAGE: &[u8] = b"message to sign";
+
+type Input<C> = (
+    Identifier<C>,
+    Option<SignatureShare<C>>,
+    SigningCommitments<C>,
+);
+
+struct Committee<C: Ciphersuite> {
+    min_signers: u16,
+    max_signers: u16,
+    public_key_package: PublicKeyPackage<C>,
+    key_packages: BTreeMap<Identifier<C>, KeyPackage<C>>,
+}
+
+impl<C: Ciphersuite> Committee<C> {
+    fn new<RNG: RngCore + CryptoRng>(min_signers: u16, max_signers: u16, rng: &mut RNG) -> Self {
+        let (secret_shares, public_key_package) =
+            keys::generate_with_dealer(max_signers, min_signers, IdentifierList::Default, rng)
+                .unwrap();
+        let key_packages = secret_shares
+            .into_iter()
+            .map(|(identifier, secret_share)| {
+                (identifier, KeyPackage::try_from(secret_share).unwrap())
+            })
+            .collect();
+
+        Self {
+            min_signers,
+            max_signers,
+            public_key_package,
+            key_packages,
+        }
+    }
+
+    fn coordinator(&self) -> Coordinator<C> {
+        Coordinator::new(
+            self.max_signers,
+            self.min_signers,
+            self.public_key_package.clone(),
+            MESSAGE.to_vec(),
+        )
+        .unwrap()
+    }
+
+    fn malicious_signers(&self, malicious_percent: u16) -> u16 {
+        let malicious_signers = (self.max_signers as u32 * malicious_percent as u32 / 100) as u16;
+        malicious_signers.min(self.max_signers - self.min_signers)
+    }
+
+    /// Runs ROAST algorithm where signers reply as soon as they have something
+    /// to send and returns all inputs of the coordinator.
+    fn run<RNG: RngCore + CryptoRng>(
+        &self,
+        malicious_signers: u16,
+        rng: &mut RNG,
+    ) -> Vec<Input<C>> {
+        let mut coordinator = self.coordinator();
+        let mut signers: BTreeMap<_, _> = self
+            .key_packages
+            .iter()
+            .enumerate()
+            .map(|(index, (identifier, key_package))| {
+                let signer = Signer::new(key_package.clone(), MESSAGE.to_vec(), rng).unwrap();
+                let is_malicious = index < malicious_signers as usize;
+                (*identifier, (signer, is_malicious))
+            })
+            .collect();
+
+        let mut pending: Vec<_> = signers.keys().copied().map(|id| (id, None)).collect();
+        let mut inputs = Vec::new();
+
+        loop {
+            let mut replies = Vec::new();
+
+            for (identifier, signing_package) in pending.drain(..) {
+                let (signer, is_malicious) = signers.get_mut(&identifier).unwrap();
+                let signature_share = signing_package.map(|signing_package: SigningPackage<C>| {
+                    if *is_malicious {
+                        let zero = <<C::Group as Group>::Field as Field>::zero();
+                        let serialization = <<C::Group as Group>::Field as Field>::serialize(&zero);
+                        signer.regenerate_signing_nonces(rng).unwrap();
+                        SignatureShare::<C>::deserialize(serialization.as_ref()).unwrap()
+                    } else {
+                        signer.receive(&signing_package, rng).unwrap()
+                    }
+                });
+                replies.push((identifier, signature_share, signer.signing_commitments()));
+            }
+
+            assert!(!replies.is_empty(), "ROAST must not stall");
+
+            for (identifier, signature_share, signing_commitments) in replies {
+                inputs.push((identifier, signature_share, signing_commitments));
+
+                match coordinator.receive(identifier, signature_share, signing_commitments) {
+                    Ok(SessionStatus::InProgress) => {}
+                    Ok(SessionStatus::Started {
+                        signers,
+                        signing_package,
+                    }) => pending.extend(
+                        signers
+                            .into_iter()
+                            .map(|identifier| (identifier, Some(signing_package.clone()))),
+                    ),
+                    Ok(SessionStatus::Finished { .. }) => return inputs,
+                    Err(Error::MaliciousSigner(_)) => {}
+                    Err(err) => panic!("{err}"),
+                }
+            }
+        }
+    }
+}
+
+/// Benchmarks [`Coordinator::receive`] throughput, [`Signer::receive`] latency
+/// and end-to-end run time of ROAST for committees of up to 1000 signers and
+/// different fractions of malicious signers.
+pub fn bench_roast<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    c: &mut Criterion,
+    name: &str,
+    rng: &mut RNG,
+) {
+    let committees: Vec<_> = COMMITTEES
+        .iter()
+        .map(|(min_signers, max_signers)| Committee::<C>::new(*min_signers, *max_signers, rng))
+        .collect();
+
+    // Signers for `Signer::receive` are set up with a separate RNG, since
+    // setup and measured routine cannot borrow the same RNG.
+    let mut setup_rng = ChaCha20Rng::seed_from_u64(rng.next_u64());
+
+    let mut group = c.benchmark_group(format!("ROAST {name}"));
+    group.sample_size(10);
+
+    for committee in &committees {
+        let committee_name = format!("{}/{}", committee.min_signers, committee.max_signers);
+
+        let (identifier, key_package) = committee.key_packages.iter().next().unwrap();
+        let signing_commitments: BTreeMap<_, _> = committee
+            .key_packages
+            .iter()
+            .take(committee.min_signers as usize)
+            .map(|(identifier, key_package)| {
+                let signer = Signer::new(key_package.clone(), MESSAGE.to_vec(), rng).unwrap();
+                (*identifier, signer.signing_commitments())
+            })
+            .collect();
+
+        group.throughput(Throughput::Elements(1));
+        group.bench_with_input(
+            BenchmarkId::new("Signer::receive", &committee_name),
+            &signing_commitments,
+            |b, signing_commitments| {
+                b.iter_batched(
+                    || {
+                        let signer =
+                            Signer::new(key_package.clone(), MESSAGE.to_vec(), &mut setup_rng)
+                                .unwrap();
+                        let mut signing_commitments = signing_commitments.clone();
+                        signing_commitments.insert(*identifier, signer.signing_commitments());
+                        (signer, SigningPackage::new(signing_commitments, MESSAGE))
+                    },
+                    |(mut signer, signing_package)| signer.receive(&signing_package, rng).unwrap(),
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+
+        for malicious_percent in MALICIOUS_PERCENTS {
+            let malicious_signers = committee.malicious_signers(malicious_percent);
+            let parameter = format!("{committee_name}, {malicious_signers} malicious");
+
+            let inputs = committee.run(malicious_signers, rng);
+
+            group.throughput(Throughput::Elements(inputs.len() as u64));
+            group.bench_with_input(
+                BenchmarkId::new("Coordinator::receive", &parameter),
+                &inputs,
+                |b, inputs| {
+                    b.iter_batched(
+                        || (committee.coordinator(), inputs.clone()),
+                        |(mut coordinator, inputs)| {
+                            for (identifier, signature_share, signing_commitments) in inputs {
+                                let _ = coordinator.receive(
+                                    identifier,
+                                    signature_share,
+                                    signing_commitments,
+                                );
+                            }
+                        },
+                        BatchSize::SmallInput,
+                    )
+                },
+            );
+
+            group.throughput(Throughput::Elements(1));
+            group.bench_with_input(
+                BenchmarkId::new("Run", &parameter),
+                &malicious_signers,
+                |b, malicious_signers| b.iter(|| committee.run(*malicious_signers, rng)),
+            );
+        }
+    }
+
+    group.finish();
+}
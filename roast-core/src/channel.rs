@@ -0,0 +1,303 @@
+use crate::{ChannelError, Envelope, Payload, SignedEnvelope};
+use alloc::{collections::BTreeMap, vec::Vec};
+use frost_core::{Ciphersuite, Identifier};
+use snow::{params::NoiseParams, Builder, HandshakeState, TransportState};
+use std::io::{Read, Write};
+use zeroize::Zeroize;
+
+/// Noise protocol used by [`SecureChannel`]: signer (initiator) knows static
+/// key of the coordinator (responder) in advance, and the coordinator learns
+/// and checks static key of the signer during handshake.
+const NOISE_PARAMS: &str = "Noise_IK_25519_ChaChaPoly_BLAKE2s";
+
+/// Maximum length of a Noise message.
+const MAX_NOISE_MESSAGE_LEN: usize = 65535;
+
+/// Length of authentication tag of a Noise transport message.
+const TAG_LEN: usize = 16;
+
+/// Maximum length of a message sent over [`SecureChannel`].
+pub const MAX_CHANNEL_MESSAGE_LEN: usize = 16 * 1024 * 1024;
+
+fn noise_params() -> Result<NoiseParams, ChannelError> {
+    Ok(NOISE_PARAMS.parse()?)
+}
+
+/// Represents static X25519 key pair of [`SecureChannel`] endpoint.
+///
+/// Private key is zeroized on drop.
+pub struct StaticKeypair {
+    private_key: [u8; 32],
+    public_key: [u8; 32],
+}
+
+impl StaticKeypair {
+    /// Generates a new [`StaticKeypair`].
+    pub fn generate() -> Result<Self, ChannelError> {
+        let mut keypair = Builder::new(noise_params()?).generate_keypair()?;
+        let mut private_key = [0; 32];
+        private_key.copy_from_slice(&keypair.private);
+        keypair.private.zeroize();
+        let mut public_key = [0; 32];
+        public_key.copy_from_slice(&keypair.public);
+
+        Ok(Self {
+            private_key,
+            public_key,
+        })
+    }
+
+    /// Returns public key to register with the other endpoint.
+    pub fn public_key(&self) -> [u8; 32] {
+        self.public_key
+    }
+}
+
+impl Drop for StaticKeypair {
+    fn drop(&mut self) {
+        self.private_key.zeroize();
+    }
+}
+
+/// Represents encrypted and mutually authenticated point-to-point channel
+/// between coordinator and signer over `stream` (e.g.
+/// [`TcpStream`](std::net::TcpStream)).
+///
+/// Every message is encrypted with ChaCha20-Poly1305 and split into Noise
+/// messages, each prefixed with its length as big-endian `u16`.
+pub struct SecureChannel<T: Read + Write> {
+    stream: T,
+    transport: TransportState,
+    remote_public_key: [u8; 32],
+}
+
+impl<T: Read + Write> SecureChannel<T> {
+    /// Performs handshake as signer with the coordinator that has static
+    /// public key `remote_public_key`.
+    pub fn initiate(
+        mut stream: T,
+        keypair: &StaticKeypair,
+        remote_public_key: [u8; 32],
+    ) -> Result<Self, ChannelError> {
+        let mut handshake = Builder::new(noise_params()?)
+            .local_private_key(&keypair.private_key)
+            .remote_public_key(&remote_public_key)
+            .build_initiator()?;
+
+        write_handshake_message(&mut stream, &mut handshake)?;
+        read_handshake_message(&mut stream, &mut handshake)?;
+
+        Ok(Self {
+            stream,
+            transport: handshake.into_transport_mode()?,
+            remote_public_key,
+        })
+    }
+
+    /// Performs handshake as coordinator with a signer whose static public
+    /// key is one of `remote_public_keys`.
+    ///
+    /// Returns [`ChannelError::UnknownPeer`] if the signer uses another
+    /// static key.
+    pub fn respond(
+        mut stream: T,
+        keypair: &StaticKeypair,
+        remote_public_keys: &[[u8; 32]],
+    ) -> Result<Self, ChannelError> {
+        let mut handshake = Builder::new(noise_params()?)
+            .local_private_key(&keypair.private_key)
+            .build_responder()?;
+
+        read_handshake_message(&mut stream, &mut handshake)?;
+        let remote_public_key = handshake
+            .get_remote_static()
+            .and_then(|remote_public_key| <[u8; 32]>::try_from(remote_public_key).ok())
+            .filter(|remote_public_key| remote_public_keys.contains(remote_public_key))
+            .ok_or(ChannelError::UnknownPeer)?;
+        write_handshake_message(&mut stream, &mut handshake)?;
+
+        Ok(Self {
+            stream,
+            transport: handshake.into_transport_mode()?,
+            remote_public_key,
+        })
+    }
+
+    /// Returns static public key of the other endpoint.
+    ///
+    /// The caller should check that it belongs to the signer whose
+    /// identifier is used in received messages (see
+    /// [`SecureChannel::receive_reply`]).
+    pub fn remote_public_key(&self) -> [u8; 32] {
+        self.remote_public_key
+    }
+
+    /// Encrypts and sends `message`.
+    pub fn send(&mut self, message: &[u8]) -> Result<(), ChannelError> {
+        if message.len() > MAX_CHANNEL_MESSAGE_LEN {
+            return Err(ChannelError::MessageTooLarge);
+        }
+
+        let mut plaintext = Vec::with_capacity(4 + message.len());
+        plaintext.extend_from_slice(&(message.len() as u32).to_be_bytes());
+        plaintext.extend_from_slice(message);
+
+        let mut buf = [0; MAX_NOISE_MESSAGE_LEN];
+        for chunk in plaintext.chunks(MAX_NOISE_MESSAGE_LEN - TAG_LEN) {
+            let len = self.transport.write_message(chunk, &mut buf)?;
+            write_frame(&mut self.stream, &buf[..len])?;
+        }
+
+        self.stream.flush()?;
+        Ok(())
+    }
+
+    /// Receives and decrypts message.
+    pub fn receive(&mut self) -> Result<Vec<u8>, ChannelError> {
+        let mut buf = [0; MAX_NOISE_MESSAGE_LEN];
+
+        let frame = read_frame(&mut self.stream)?;
+        let len = self.transport.read_message(&frame, &mut buf)?;
+        if len < 4 {
+            return Err(ChannelError::MalformedMessage);
+        }
+        let (message_len, rest) = buf[..len].split_at(4);
+        let message_len = u32::from_be_bytes([
+            message_len[0],
+            message_len[1],
+            message_len[2],
+            message_len[3],
+        ]) as usize;
+        if message_len > MAX_CHANNEL_MESSAGE_LEN || rest.len() > message_len {
+            return Err(ChannelError::MalformedMessage);
+        }
+
+        let mut message = Vec::with_capacity(message_len);
+        message.extend_from_slice(rest);
+        while message.len() < message_len {
+            let frame = read_frame(&mut self.stream)?;
+            let len = self.transport.read_message(&frame, &mut buf)?;
+            if message.len() + len > message_len {
+                return Err(ChannelError::MalformedMessage);
+            }
+            message.extend_from_slice(&buf[..len]);
+        }
+
+        Ok(message)
+    }
+
+    /// Sends `envelope` in compact encoding (see [`Envelope::to_compact`]).
+    pub fn send_envelope<C: Ciphersuite>(
+        &mut self,
+        envelope: &Envelope<C>,
+    ) -> Result<(), ChannelError> {
+        let message = envelope
+            .to_compact()
+            .map_err(|_| ChannelError::MalformedMessage)?;
+        self.send(&message)
+    }
+
+    /// Receives [`Envelope`] in compact encoding (see
+    /// [`Envelope::decode_compact`]).
+    pub fn receive_envelope<C: Ciphersuite>(&mut self) -> Result<Envelope<C>, ChannelError> {
+        Envelope::decode_compact(&self.receive()?).map_err(|_| ChannelError::MalformedMessage)
+    }
+
+    /// Sends `signed_envelope` in compact encoding (see
+    /// [`SignedEnvelope::to_compact`]).
+    pub fn send_signed_envelope<C: Ciphersuite>(
+        &mut self,
+        signed_envelope: &SignedEnvelope<C>,
+    ) -> Result<(), ChannelError> {
+        let message = signed_envelope
+            .to_compact()
+            .map_err(|_| ChannelError::MalformedMessage)?;
+        self.send(&message)
+    }
+
+    /// Receives [`SignedEnvelope`] in compact encoding (see
+    /// [`SignedEnvelope::decode_compact`]) without verifying its signature.
+    pub fn receive_signed_envelope<C: Ciphersuite>(
+        &mut self,
+    ) -> Result<SignedEnvelope<C>, ChannelError> {
+        SignedEnvelope::decode_compact(&self.receive()?).map_err(|_| ChannelError::MalformedMessage)
+    }
+
+    /// Same as [`SecureChannel::receive_envelope`], but also checks that the
+    /// envelope is a reply of the signer whose identifier is registered for
+    /// static key of the other endpoint in `identifiers`.
+    ///
+    /// Returns [`ChannelError::UnknownPeer`] if static key of the other
+    /// endpoint is not registered and [`ChannelError::SenderMismatch`] if the
+    /// envelope is not a reply of this signer.
+    pub fn receive_reply<C: Ciphersuite>(
+        &mut self,
+        identifiers: &BTreeMap<[u8; 32], Identifier<C>>,
+    ) -> Result<Envelope<C>, ChannelError> {
+        let envelope = self.receive_envelope()?;
+        self.check_sender(identifiers, &envelope)?;
+        Ok(envelope)
+    }
+
+    /// Same as [`SecureChannel::receive_reply`], but receives
+    /// [`SignedEnvelope`] (see [`SecureChannel::receive_signed_envelope`]).
+    pub fn receive_signed_reply<C: Ciphersuite>(
+        &mut self,
+        identifiers: &BTreeMap<[u8; 32], Identifier<C>>,
+    ) -> Result<SignedEnvelope<C>, ChannelError> {
+        let signed_envelope = self.receive_signed_envelope()?;
+        self.check_sender(identifiers, signed_envelope.envelope())?;
+        Ok(signed_envelope)
+    }
+
+    fn check_sender<C: Ciphersuite>(
+        &self,
+        identifiers: &BTreeMap<[u8; 32], Identifier<C>>,
+        envelope: &Envelope<C>,
+    ) -> Result<(), ChannelError> {
+        let identifier = identifiers
+            .get(&self.remote_public_key)
+            .ok_or(ChannelError::UnknownPeer)?;
+
+        match envelope.payload() {
+            Payload::Reply {
+                identifier: sender, ..
+            } if sender == identifier => Ok(()),
+            _ => Err(ChannelError::SenderMismatch),
+        }
+    }
+}
+
+fn write_handshake_message<T: Write>(
+    stream: &mut T,
+    handshake: &mut HandshakeState,
+) -> Result<(), ChannelError> {
+    let mut buf = [0; MAX_NOISE_MESSAGE_LEN];
+    let len = handshake.write_message(&[], &mut buf)?;
+    write_frame(stream, &buf[..len])?;
+    stream.flush()?;
+    Ok(())
+}
+
+fn read_handshake_message<T: Read>(
+    stream: &mut T,
+    handshake: &mut HandshakeState,
+) -> Result<(), ChannelError> {
+    let mut buf = [0; MAX_NOISE_MESSAGE_LEN];
+    handshake.read_message(&read_frame(stream)?, &mut buf)?;
+    Ok(())
+}
+
+fn write_frame<T: Write>(stream: &mut T, frame: &[u8]) -> Result<(), ChannelError> {
+    stream.write_all(&(frame.len() as u16).to_be_bytes())?;
+    stream.write_all(frame)?;
+    Ok(())
+}
+
+fn read_frame<T: Read>(stream: &mut T) -> Result<Vec<u8>, ChannelError> {
+    let mut len = [0; 2];
+    stream.read_exact(&mut len)?;
+    let mut frame = alloc::vec![0; u16::from_be_bytes(len) as usize];
+    stream.read_exact(&mut frame)?;
+    Ok(frame)
+}
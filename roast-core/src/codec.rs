@@ -0,0 +1,100 @@
+use crate::Error;
+use alloc::vec::Vec;
+use frost_core::Ciphersuite;
+
+pub(crate) struct Writer<C: Ciphersuite> {
+    bytes: Vec<u8>,
+    error: Error<C>,
+}
+
+impl<C: Ciphersuite> Writer<C> {
+    /// Creates a new [`Writer`] that fails with `error`.
+    pub(crate) fn new(error: Error<C>) -> Self {
+        Self {
+            bytes: Vec::new(),
+            error,
+        }
+    }
+
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    pub(crate) fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    pub(crate) fn u16(&mut self, value: u16) {
+        self.bytes.extend_from_slice(&value.to_be_bytes());
+    }
+
+    pub(crate) fn array(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    pub(crate) fn len(&mut self, len: usize) -> Result<(), Error<C>> {
+        let len = u32::try_from(len).map_err(|_| self.error)?;
+        self.bytes.extend_from_slice(&len.to_be_bytes());
+        Ok(())
+    }
+
+    pub(crate) fn bytes(&mut self, bytes: &[u8]) -> Result<(), Error<C>> {
+        self.len(bytes.len())?;
+        self.bytes.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+pub(crate) struct Reader<'a, C: Ciphersuite> {
+    bytes: &'a [u8],
+    error: Error<C>,
+}
+
+impl<'a, C: Ciphersuite> Reader<'a, C> {
+    /// Creates a new [`Reader`] that fails with `error`.
+    pub(crate) fn new(bytes: &'a [u8], error: Error<C>) -> Self {
+        Self { bytes, error }
+    }
+
+    /// Checks that all bytes have been read.
+    pub(crate) fn finish(self) -> Result<(), Error<C>> {
+        if !self.bytes.is_empty() {
+            return Err(self.error);
+        }
+        Ok(())
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error<C>> {
+        if self.bytes.len() < len {
+            return Err(self.error);
+        }
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(bytes)
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8, Error<C>> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub(crate) fn u16(&mut self) -> Result<u16, Error<C>> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32, Error<C>> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    pub(crate) fn array<const N: usize>(&mut self) -> Result<[u8; N], Error<C>> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    pub(crate) fn bytes(&mut self) -> Result<&'a [u8], Error<C>> {
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+}
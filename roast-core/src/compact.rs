@@ -0,0 +1,301 @@
+use crate::{Envelope, Error, Payload, RunId, SignedEnvelope, PROTOCOL_VERSION};
+use alloc::{collections::BTreeMap, vec::Vec};
+use frost_core::{
+    round1::{NonceCommitment, SigningCommitments},
+    round2::SignatureShare,
+    Ciphersuite, Identifier, Signature, SigningPackage,
+};
+
+const REPLY: u8 = 0;
+const SIGNING_PACKAGE: u8 = 1;
+
+/// Writes into `buf`, or only counts written bytes if `buf` is `None`.
+struct Writer<'a, C: Ciphersuite> {
+    buf: Option<&'a mut [u8]>,
+    len: usize,
+    error: Error<C>,
+}
+
+impl<'a, C: Ciphersuite> Writer<'a, C> {
+    /// Writes with `write` into `buf`, or only counts written bytes if `buf`
+    /// is `None`, and returns the number of written bytes.
+    fn encode(
+        buf: Option<&'a mut [u8]>,
+        write: impl FnOnce(&mut Self) -> Result<(), Error<C>>,
+    ) -> Result<usize, Error<C>> {
+        let mut writer = Self {
+            buf,
+            len: 0,
+            error: Error::BufferTooSmall,
+        };
+        write(&mut writer)?;
+        Ok(writer.len)
+    }
+
+    fn raw(&mut self, bytes: &[u8]) -> Result<(), Error<C>> {
+        let end = self.len + bytes.len();
+        if let Some(buf) = self.buf.as_deref_mut() {
+            buf.get_mut(self.len..end)
+                .ok_or(self.error)?
+                .copy_from_slice(bytes);
+        }
+        self.len = end;
+        Ok(())
+    }
+
+    fn varint(&mut self, mut value: u64) -> Result<(), Error<C>> {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                return self.raw(&[byte]);
+            }
+            self.raw(&[byte | 0x80])?;
+        }
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) -> Result<(), Error<C>> {
+        self.varint(bytes.len() as u64)?;
+        self.raw(bytes)
+    }
+
+    fn signing_commitments(
+        &mut self,
+        signing_commitments: &SigningCommitments<C>,
+    ) -> Result<(), Error<C>> {
+        self.bytes(&signing_commitments.hiding().serialize()?)?;
+        self.bytes(&signing_commitments.binding().serialize()?)
+    }
+
+    fn envelope(&mut self, envelope: &Envelope<C>) -> Result<(), Error<C>> {
+        self.varint(PROTOCOL_VERSION.into())?;
+        self.bytes(C::ID.as_bytes())?;
+        self.raw(envelope.run_id().as_ref())?;
+        self.varint(envelope.session_id().into())?;
+
+        match envelope.payload() {
+            Payload::Reply {
+                identifier,
+                signature_share,
+                signing_commitments,
+            } => {
+                self.raw(&[REPLY])?;
+                self.bytes(identifier.serialize().as_ref())?;
+                match signature_share {
+                    Some(signature_share) => {
+                        self.raw(&[1])?;
+                        self.bytes(signature_share.serialize().as_ref())?;
+                    }
+                    None => self.raw(&[0])?,
+                }
+                self.signing_commitments(signing_commitments)
+            }
+            Payload::SigningPackage(signing_package) => {
+                self.raw(&[SIGNING_PACKAGE])?;
+                self.varint(signing_package.signing_commitments().len() as u64)?;
+                for (identifier, signing_commitments) in signing_package.signing_commitments() {
+                    self.bytes(identifier.serialize().as_ref())?;
+                    self.signing_commitments(signing_commitments)?;
+                }
+                self.bytes(signing_package.message())
+            }
+        }
+    }
+
+    fn signed_envelope(&mut self, signed_envelope: &SignedEnvelope<C>) -> Result<(), Error<C>> {
+        self.envelope(signed_envelope.envelope())?;
+        self.bytes(&signed_envelope.signature().serialize()?)
+    }
+}
+
+/// Writes with `write` into a buffer of exact length: the first pass counts
+/// written bytes and the second one writes them.
+fn to_vec<C: Ciphersuite>(
+    write: impl Fn(&mut Writer<'_, C>) -> Result<(), Error<C>>,
+) -> Result<Vec<u8>, Error<C>> {
+    let mut buf = alloc::vec![0; Writer::encode(None, &write)?];
+    Writer::encode(Some(&mut buf), &write)?;
+    Ok(buf)
+}
+
+struct Reader<'a, C: Ciphersuite> {
+    bytes: &'a [u8],
+    error: Error<C>,
+}
+
+impl<'a, C: Ciphersuite> Reader<'a, C> {
+    fn raw(&mut self, len: usize) -> Result<&'a [u8], Error<C>> {
+        if self.bytes.len() < len {
+            return Err(self.error);
+        }
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, Error<C>> {
+        Ok(self.raw(1)?[0])
+    }
+
+    /// Reads varint and rejects overlong and non-minimal encodings, so that
+    /// every value has exactly one encoding.
+    fn varint(&mut self) -> Result<u64, Error<C>> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            let bits = u64::from(byte & 0x7f);
+            if shift == 63 && bits > 1 {
+                return Err(self.error);
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                if byte == 0 && shift != 0 {
+                    return Err(self.error);
+                }
+                return Ok(value);
+            }
+        }
+        Err(self.error)
+    }
+
+    fn u16(&mut self) -> Result<u16, Error<C>> {
+        u16::try_from(self.varint()?).map_err(|_| self.error)
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], Error<C>> {
+        let len = usize::try_from(self.varint()?).map_err(|_| self.error)?;
+        self.raw(len)
+    }
+
+    fn signing_commitments(&mut self) -> Result<SigningCommitments<C>, Error<C>> {
+        let hiding = NonceCommitment::deserialize(self.bytes()?)?;
+        let binding = NonceCommitment::deserialize(self.bytes()?)?;
+        Ok(SigningCommitments::new(hiding, binding))
+    }
+
+    fn envelope(&mut self) -> Result<Envelope<C>, Error<C>> {
+        let version = self.u16()?;
+        if version != PROTOCOL_VERSION {
+            return Err(Error::UnsupportedProtocolVersion(version));
+        }
+        if self.bytes()? != C::ID.as_bytes() {
+            return Err(Error::CiphersuiteMismatch);
+        }
+
+        let mut run_id = [0; 32];
+        run_id.copy_from_slice(self.raw(run_id.len())?);
+        let session_id = self.u16()?;
+
+        let payload = match self.u8()? {
+            REPLY => {
+                let identifier = Identifier::deserialize(self.bytes()?)?;
+                let signature_share = match self.u8()? {
+                    0 => None,
+                    1 => Some(SignatureShare::deserialize(self.bytes()?)?),
+                    _ => return Err(self.error),
+                };
+                let signing_commitments = self.signing_commitments()?;
+
+                Payload::Reply {
+                    identifier,
+                    signature_share,
+                    signing_commitments,
+                }
+            }
+            SIGNING_PACKAGE => {
+                let mut signing_commitments = BTreeMap::new();
+                for _ in 0..self.varint()? {
+                    let identifier = Identifier::deserialize(self.bytes()?)?;
+                    // Identifiers must be strictly increasing, as written by
+                    // `Writer`, so that encoding stays canonical.
+                    if signing_commitments
+                        .last_key_value()
+                        .is_some_and(|(last, _)| *last >= identifier)
+                    {
+                        return Err(self.error);
+                    }
+                    signing_commitments.insert(identifier, self.signing_commitments()?);
+                }
+                let message = self.bytes()?;
+
+                Payload::SigningPackage(SigningPackage::new(signing_commitments, message))
+            }
+            _ => return Err(self.error),
+        };
+
+        Ok(Envelope::new(RunId::new(run_id), session_id, payload))
+    }
+
+    fn signed_envelope(&mut self) -> Result<SignedEnvelope<C>, Error<C>> {
+        let envelope = self.envelope()?;
+        let signature = Signature::deserialize(self.bytes()?)?;
+        Ok(SignedEnvelope::new(envelope, signature))
+    }
+
+    /// Checks that all bytes have been read.
+    fn finish<T>(self, value: T) -> Result<T, Error<C>> {
+        if !self.bytes.is_empty() {
+            return Err(self.error);
+        }
+        Ok(value)
+    }
+}
+
+impl<C: Ciphersuite> Envelope<C> {
+    /// Returns length of compact encoding of [`Envelope`].
+    pub fn compact_len(&self) -> Result<usize, Error<C>> {
+        Writer::encode(None, |writer| writer.envelope(self))
+    }
+
+    /// Encodes [`Envelope`] into `buf` in compact encoding and returns the
+    /// number of written bytes.
+    ///
+    /// Returns [`Error::BufferTooSmall`] if `buf` is shorter than
+    /// [`Envelope::compact_len`].
+    pub fn encode_compact(&self, buf: &mut [u8]) -> Result<usize, Error<C>> {
+        Writer::encode(Some(buf), |writer| writer.envelope(self))
+    }
+
+    /// Encodes [`Envelope`] in compact encoding.
+    ///
+    /// Compact encoding does not depend on `serialization` feature. Integers
+    /// and lengths are encoded as LEB128 varints, FROST types as their
+    /// canonical element and scalar encodings.
+    pub fn to_compact(&self) -> Result<Vec<u8>, Error<C>> {
+        to_vec(|writer| writer.envelope(self))
+    }
+
+    /// Decodes [`Envelope`] from compact encoding produced by
+    /// [`Envelope::encode_compact`].
+    ///
+    /// Rejects messages of other protocol versions with
+    /// [`Error::UnsupportedProtocolVersion`] and messages of other
+    /// ciphersuites with [`Error::CiphersuiteMismatch`].
+    pub fn decode_compact(bytes: &[u8]) -> Result<Self, Error<C>> {
+        let mut reader = Reader {
+            bytes,
+            error: Error::MalformedMessage,
+        };
+        let envelope = reader.envelope()?;
+        reader.finish(envelope)
+    }
+}
+
+impl<C: Ciphersuite> SignedEnvelope<C> {
+    /// Encodes [`SignedEnvelope`] in compact encoding, i.e. compact encoding
+    /// of the envelope followed by its signature.
+    pub fn to_compact(&self) -> Result<Vec<u8>, Error<C>> {
+        to_vec(|writer| writer.signed_envelope(self))
+    }
+
+    /// Decodes [`SignedEnvelope`] from compact encoding produced by
+    /// [`SignedEnvelope::to_compact`] without verifying its signature.
+    pub fn decode_compact(bytes: &[u8]) -> Result<Self, Error<C>> {
+        let mut reader = Reader {
+            bytes,
+            error: Error::MalformedMessage,
+        };
+        let signed_envelope = reader.signed_envelope()?;
+        reader.finish(signed_envelope)
+    }
+}
@@ -1,15 +1,21 @@
-use crate::{Error, MaliciousSignerError};
+use crate::{
+    Envelope, Error, MaliciousSignerError, Metrics, Payload, ReceiveManyError, RunId,
+    SignedEnvelope,
+};
 use alloc::{
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, VecDeque},
     vec::Vec,
 };
 use core::mem;
 use frost_core::{
-    keys::PublicKeyPackage, round1::SigningCommitments, round2::SignatureShare, Ciphersuite,
-    Error as FrostError, Identifier, Signature, SigningPackage,
+    keys::{PublicKeyPackage, VerifyingShare},
+    round1::SigningCommitments,
+    round2::SignatureShare,
+    Ciphersuite, Error as FrostError, Identifier, Signature, SigningPackage, VerifyingKey,
 };
 
-type SessionId = u16;
+/// Represents session identifier.
+pub type SessionId = u16;
 
 #[derive(Debug)]
 struct Session<C: Ciphersuite> {
@@ -37,19 +43,109 @@ pub enum SessionStatus<C: Ciphersuite> {
     },
 }
 
+/// Represents all possible signer statuses.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SignerStatus {
+    /// Coordinator has not received anything from signer yet.
+    Unknown,
+    /// Signer replied to coordinator and waits for a new session.
+    Responsive,
+    /// Signer participates in session and coordinator waits for its
+    /// [`SignatureShare`].
+    InSession(SessionId),
+    /// Signer is marked as malicious.
+    Malicious(MaliciousSignerError),
+}
+
+/// Represents event that occurred while processing batch of replies (see
+/// [`Coordinator::receive_many`]).
+#[derive(Debug)]
+pub enum CoordinatorEvent<C: Ciphersuite> {
+    /// Session started with `signers` and `signing_package`.
+    SessionStarted {
+        /// Identifier of the session.
+        session_id: SessionId,
+        /// Set of signers with which session started.
+        signers: BTreeSet<Identifier<C>>,
+        /// Signing package (includes [`SigningCommitments`] from all signers
+        /// and message to sign).
+        signing_package: SigningPackage<C>,
+    },
+    /// Signer is marked as malicious.
+    SignerMarkedMalicious {
+        /// Identifier of the signer.
+        identifier: Identifier<C>,
+        /// Reason why the signer is marked as malicious.
+        malicious_signer_error: MaliciousSignerError,
+    },
+    /// Session finished.
+    Finished {
+        /// Final signature.
+        signature: Signature<C>,
+    },
+}
+
+/// Represents read-only view of session.
+#[derive(Debug)]
+pub struct SessionInfo<'a, C: Ciphersuite> {
+    session_id: SessionId,
+    session: &'a Session<C>,
+}
+
+impl<'a, C: Ciphersuite> SessionInfo<'a, C> {
+    /// Returns identifier of the session.
+    pub fn session_id(&self) -> SessionId {
+        self.session_id
+    }
+
+    /// Returns signing package of the session.
+    pub fn signing_package(&self) -> &'a SigningPackage<C> {
+        &self.session.signing_package
+    }
+
+    /// Returns signers with which session started.
+    pub fn signers(&self) -> impl Iterator<Item = Identifier<C>> + 'a {
+        let session = self.session;
+        session
+            .signing_package
+            .signing_commitments()
+            .keys()
+            .copied()
+    }
+
+    /// Returns received and verified signature shares.
+    pub fn signature_shares(&self) -> &'a BTreeMap<Identifier<C>, SignatureShare<C>> {
+        &self.session.signature_shares
+    }
+
+    /// Returns signers whose signature shares are still missing.
+    pub fn missing_signers(&self) -> impl Iterator<Item = Identifier<C>> + 'a {
+        let session = self.session;
+        let signature_shares = &session.signature_shares;
+        self.signers()
+            .filter(move |identifier| !signature_shares.contains_key(identifier))
+    }
+}
+
 /// Represents coordinator.
+///
+/// Reports metrics of the run to `M` (see [`Metrics`]).
 #[derive(Debug)]
-pub struct Coordinator<C: Ciphersuite> {
+pub struct Coordinator<C: Ciphersuite, M: Metrics = ()> {
     max_signers: u16,
     min_signers: u16,
     public_key_package: PublicKeyPackage<C>,
     message: Vec<u8>,
+    run_id: RunId,
+    identity_keys: BTreeMap<Identifier<C>, VerifyingKey<C>>,
     responsive_signers: BTreeSet<Identifier<C>>,
     malicious_signers: BTreeMap<Identifier<C>, MaliciousSignerError>,
-    latest_signing_commitments: BTreeMap<Identifier<C>, SigningCommitments<C>>,
+    signing_commitments: BTreeMap<Identifier<C>, VecDeque<SigningCommitments<C>>>,
+    received_signing_commitments: BTreeMap<Vec<u8>, Identifier<C>>,
     session_counter: SessionId,
     signer_session: BTreeMap<Identifier<C>, SessionId>,
     session: BTreeMap<SessionId, Session<C>>,
+    metrics: M,
 }
 
 impl<C: Ciphersuite> Coordinator<C> {
@@ -59,6 +155,20 @@ impl<C: Ciphersuite> Coordinator<C> {
         min_signers: u16,
         public_key_package: PublicKeyPackage<C>,
         message: Vec<u8>,
+    ) -> Result<Self, Error<C>> {
+        Self::with_metrics(max_signers, min_signers, public_key_package, message, ())
+    }
+}
+
+impl<C: Ciphersuite, M: Metrics> Coordinator<C, M> {
+    /// Creates a new [`Coordinator`] that reports metrics of the run to
+    /// `metrics`.
+    pub fn with_metrics(
+        max_signers: u16,
+        min_signers: u16,
+        public_key_package: PublicKeyPackage<C>,
+        message: Vec<u8>,
+        mut metrics: M,
     ) -> Result<Self, Error<C>> {
         if min_signers < 2 {
             return Err(Error::Frost(FrostError::InvalidMinSigners));
@@ -72,20 +182,52 @@ impl<C: Ciphersuite> Coordinator<C> {
             return Err(Error::Frost(FrostError::InvalidMinSigners));
         }
 
+        let run_id = RunId::derive(public_key_package.verifying_key(), &message)?;
+
+        metrics.run_started();
+
         Ok(Self {
             max_signers,
             min_signers,
             public_key_package,
             message,
+            run_id,
+            identity_keys: BTreeMap::new(),
             responsive_signers: BTreeSet::new(),
             malicious_signers: BTreeMap::new(),
-            latest_signing_commitments: BTreeMap::new(),
+            signing_commitments: BTreeMap::new(),
+            received_signing_commitments: BTreeMap::new(),
             session_counter: 0,
             signer_session: BTreeMap::new(),
             session: BTreeMap::new(),
+            metrics,
         })
     }
 
+    /// Sets identifier of coordinator run.
+    ///
+    /// By default it is derived from verifying key and message (see
+    /// [`RunId::derive`]). Signers must use the same [`RunId`] (see
+    /// [`Signer::with_run_id`](crate::Signer::with_run_id)).
+    pub fn with_run_id(mut self, run_id: RunId) -> Self {
+        self.run_id = run_id;
+        self
+    }
+
+    /// Registers long-term identity keys of signers.
+    ///
+    /// Once registered, replies are accepted only if they are signed with
+    /// identity key of the sender (see [`Coordinator::receive_signed_envelope`]).
+    /// Other methods that receive replies or [`SigningCommitments`] of signers
+    /// return [`Error::UnsignedEnvelope`] then.
+    pub fn with_identity_keys(
+        mut self,
+        identity_keys: BTreeMap<Identifier<C>, VerifyingKey<C>>,
+    ) -> Self {
+        self.identity_keys = identity_keys;
+        self
+    }
+
     /// Receives the [`Identifier`], [`Option<SignatureShare<C>>`] and
     /// [`SigningCommitments`] from the signer.
     ///
@@ -100,16 +242,387 @@ impl<C: Ciphersuite> Coordinator<C> {
     /// - If the coordinator has not yet received threshold number of
     ///   [`SigningCommitments`] or [`SignatureShare`], session goes to state
     ///   [`SessionStatus::InProgress`].
+    ///
+    /// Returns [`Error::Frost`] with [`FrostError::UnknownIdentifier`] without
+    /// marking anyone as malicious if the signer is not in
+    /// [`PublicKeyPackage`].
+    ///
+    /// Returns [`Error::UnsignedEnvelope`] if identity keys of signers are
+    /// registered (see [`Coordinator::with_identity_keys`]).
     pub fn receive(
         &mut self,
         identifier: Identifier<C>,
         signature_share: Option<SignatureShare<C>>,
         signing_commitments: SigningCommitments<C>,
     ) -> Result<SessionStatus<C>, Error<C>> {
+        if !self.identity_keys.is_empty() {
+            return Err(Error::UnsignedEnvelope);
+        }
+
+        self.receive_with_verification(identifier, signature_share, signing_commitments, None)
+    }
+
+    /// Same as [`Coordinator::receive`], but receives reply of the signer
+    /// wrapped in [`Envelope`].
+    ///
+    /// Returns [`Error::RunIdMismatch`] if the message belongs to another
+    /// coordinator run, [`Error::SessionIdMismatch`] if it does not belong to
+    /// the current session of the signer (`0` if the signer is not in session)
+    /// and [`Error::ReplayedEnvelope`] if it carries [`SigningCommitments`]
+    /// that were already received from the signer. In all these cases the
+    /// signer is not marked as malicious, since such messages can be replayed
+    /// by anyone.
+    ///
+    /// Returns [`Error::UnsignedEnvelope`] if identity keys of signers are
+    /// registered (see [`Coordinator::with_identity_keys`]).
+    pub fn receive_envelope(
+        &mut self,
+        envelope: Envelope<C>,
+    ) -> Result<SessionStatus<C>, Error<C>> {
+        if !self.identity_keys.is_empty() {
+            return Err(Error::UnsignedEnvelope);
+        }
+
+        self.receive_verified_envelope(envelope)
+    }
+
+    /// Same as [`Coordinator::receive_envelope`], but receives reply of the
+    /// signer signed with its long-term identity key.
+    ///
+    /// Signature is verified before any other check, so that nobody but the
+    /// signer itself can get it marked as malicious. Returns
+    /// [`Error::MissingIdentityKey`] if no identity key is registered for the
+    /// sender and [`Error::InvalidEnvelopeSignature`] if signature is invalid.
+    pub fn receive_signed_envelope(
+        &mut self,
+        signed_envelope: SignedEnvelope<C>,
+    ) -> Result<SessionStatus<C>, Error<C>> {
+        let Payload::Reply { identifier, .. } = signed_envelope.envelope().payload() else {
+            return Err(Error::MalformedMessage);
+        };
+
+        let identity_key = self
+            .identity_keys
+            .get(identifier)
+            .ok_or(Error::MissingIdentityKey)?;
+        signed_envelope.verify(identity_key)?;
+
+        self.receive_verified_envelope(signed_envelope.into_envelope())
+    }
+
+    /// Receives reply of the signer wrapped in [`Envelope`] whose sender is
+    /// either authenticated or not required to be.
+    fn receive_verified_envelope(
+        &mut self,
+        envelope: Envelope<C>,
+    ) -> Result<SessionStatus<C>, Error<C>> {
+        let Payload::Reply {
+            identifier,
+            signature_share,
+            signing_commitments,
+        } = envelope.payload()
+        else {
+            return Err(Error::MalformedMessage);
+        };
+
+        let session_id = match self.signer_status(identifier) {
+            SignerStatus::InSession(session_id) => session_id,
+            _ => 0,
+        };
+        envelope.verify(self.run_id, session_id)?;
+
+        // The first reply of the signer can be replayed while it waits for a
+        // session, so replays are detected before the signer can be marked as
+        // malicious for an unsolicited reply. Replies of a session cannot be
+        // replayed, since the signer leaves the session once one is received.
+        if session_id == 0
+            && self
+                .received_signing_commitments
+                .get(&Self::serialize_signing_commitments(signing_commitments)?)
+                == Some(identifier)
+        {
+            return Err(Error::ReplayedEnvelope);
+        }
+
+        self.receive_with_verification(*identifier, *signature_share, *signing_commitments, None)
+    }
+
+    /// Returns [`SigningPackage`] of the session wrapped in [`Envelope`] to
+    /// send to signers of the session.
+    pub fn envelope(&self, session_id: SessionId) -> Option<Envelope<C>> {
+        self.session.get(&session_id).map(|session| {
+            Envelope::new(
+                self.run_id,
+                session_id,
+                Payload::SigningPackage(session.signing_package.clone()),
+            )
+        })
+    }
+
+    /// Same as [`Coordinator::receive`] for each reply of the batch in order,
+    /// but signature shares are verified in parallel if `rayon` feature is
+    /// enabled.
+    ///
+    /// Returns result of [`Coordinator::receive`] for each reply in order.
+    /// State transitions are exactly the same as for sequential processing.
+    ///
+    /// Bounds are required for parallel verification and hold for all
+    /// ciphersuites. They do not depend on `rayon` feature, so that enabling it
+    /// does not break generic code.
+    pub fn receive_batch(
+        &mut self,
+        replies: Vec<(
+            Identifier<C>,
+            Option<SignatureShare<C>>,
+            SigningCommitments<C>,
+        )>,
+    ) -> Vec<Result<SessionStatus<C>, Error<C>>>
+    where
+        Identifier<C>: Sync,
+        SignatureShare<C>: Sync,
+        SigningPackage<C>: Sync,
+        VerifyingShare<C>: Sync,
+        VerifyingKey<C>: Sync,
+    {
+        if !self.identity_keys.is_empty() {
+            return replies
+                .iter()
+                .map(|_| Err(Error::UnsignedEnvelope))
+                .collect();
+        }
+
+        #[cfg(feature = "rayon")]
+        let verification_results = self.verify_signature_shares(&replies);
+        #[cfg(not(feature = "rayon"))]
+        let verification_results = BTreeMap::new();
+
+        self.receive_batch_with_verification(replies, verification_results)
+    }
+
+    /// Verifies signature shares of the batch that can be verified before
+    /// processing the batch (see `signature_share_tasks`) in parallel and
+    /// returns verification results by identifier of signer and session id.
+    #[cfg(feature = "rayon")]
+    fn verify_signature_shares(
+        &self,
+        replies: &[(
+            Identifier<C>,
+            Option<SignatureShare<C>>,
+            SigningCommitments<C>,
+        )],
+    ) -> BTreeMap<(Identifier<C>, SessionId), bool>
+    where
+        Identifier<C>: Sync,
+        SignatureShare<C>: Sync,
+        SigningPackage<C>: Sync,
+        VerifyingShare<C>: Sync,
+        VerifyingKey<C>: Sync,
+    {
+        use rayon::prelude::*;
+
+        let verifying_key = self.public_key_package.verifying_key();
+        self.signature_share_tasks(replies)
+            .into_par_iter()
+            .map(
+                |(identifier, session_id, signature_share, signing_package, verifying_share)| {
+                    let is_valid = frost_core::verify_signature_share(
+                        *identifier,
+                        verifying_share,
+                        signature_share,
+                        signing_package,
+                        verifying_key,
+                    )
+                    .is_ok();
+                    ((*identifier, session_id), is_valid)
+                },
+            )
+            .collect::<Vec<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Receives a batch of replies from signers and returns all resulting
+    /// [`CoordinatorEvent`]s in order.
+    ///
+    /// Replies are processed as in [`Coordinator::receive_batch`]. Replies of
+    /// signers that are already marked as malicious are ignored. If any reply
+    /// fails with an error other than [`Error::MaliciousSigner`] (e.g.
+    /// [`Error::TooManyMaliciousSigners`]), returns the first such error
+    /// together with events of the whole batch (see [`ReceiveManyError`]).
+    pub fn receive_many(
+        &mut self,
+        replies: Vec<(
+            Identifier<C>,
+            Option<SignatureShare<C>>,
+            SigningCommitments<C>,
+        )>,
+    ) -> Result<Vec<CoordinatorEvent<C>>, ReceiveManyError<C>>
+    where
+        Identifier<C>: Sync,
+        SignatureShare<C>: Sync,
+        SigningPackage<C>: Sync,
+        VerifyingShare<C>: Sync,
+        VerifyingKey<C>: Sync,
+    {
+        let identifiers: Vec<_> = replies
+            .iter()
+            .map(|(identifier, _, _)| *identifier)
+            .collect();
+        let session_counter = self.session_counter;
+        let malicious_signers = self.malicious_signers.keys().copied().collect();
+        let results = self.receive_batch(replies);
+
+        Self::events(identifiers, session_counter, malicious_signers, results)
+    }
+
+    /// Converts results of [`Coordinator::receive_batch`] into
+    /// [`CoordinatorEvent`]s.
+    fn events(
+        identifiers: Vec<Identifier<C>>,
+        mut session_counter: SessionId,
+        mut malicious_signers: BTreeSet<Identifier<C>>,
+        results: Vec<Result<SessionStatus<C>, Error<C>>>,
+    ) -> Result<Vec<CoordinatorEvent<C>>, ReceiveManyError<C>> {
+        let mut events = Vec::new();
+        let mut error = None;
+
+        for (identifier, result) in identifiers.into_iter().zip(results) {
+            match result {
+                Ok(SessionStatus::InProgress) => {}
+                Ok(SessionStatus::Started {
+                    signers,
+                    signing_package,
+                }) => {
+                    session_counter += 1;
+                    events.push(CoordinatorEvent::SessionStarted {
+                        session_id: session_counter,
+                        signers,
+                        signing_package,
+                    });
+                }
+                Ok(SessionStatus::Finished { signature }) => {
+                    events.push(CoordinatorEvent::Finished { signature });
+                }
+                Err(Error::MaliciousSigner(malicious_signer_error)) => {
+                    if malicious_signers.insert(identifier) {
+                        events.push(CoordinatorEvent::SignerMarkedMalicious {
+                            identifier,
+                            malicious_signer_error,
+                        });
+                    }
+                }
+                Err(err) => {
+                    error.get_or_insert(err);
+                }
+            }
+        }
+
+        match error {
+            Some(error) => Err(ReceiveManyError { events, error }),
+            None => Ok(events),
+        }
+    }
+
+    /// Returns signature shares of the batch that can be verified before
+    /// processing the batch, i.e. the first signature share of each signer
+    /// that participates in a session, with the current session id of the
+    /// signer.
+    ///
+    /// Earlier replies of the batch can start a new session of the signer, so
+    /// verification result is used only if the session of the signer is still
+    /// the same when its reply is processed.
+    #[cfg(feature = "rayon")]
+    #[allow(clippy::type_complexity)]
+    fn signature_share_tasks<'a>(
+        &'a self,
+        replies: &'a [(
+            Identifier<C>,
+            Option<SignatureShare<C>>,
+            SigningCommitments<C>,
+        )],
+    ) -> Vec<(
+        &'a Identifier<C>,
+        SessionId,
+        &'a SignatureShare<C>,
+        &'a SigningPackage<C>,
+        &'a VerifyingShare<C>,
+    )> {
+        let mut seen = BTreeSet::new();
+
+        replies
+            .iter()
+            .filter(|(identifier, _, _)| seen.insert(*identifier))
+            .filter_map(|(identifier, signature_share, _)| {
+                let signature_share = signature_share.as_ref()?;
+                let session_id = *self.signer_session.get(identifier)?;
+                let session = self.session.get(&session_id)?;
+                let verifying_share = self.public_key_package.verifying_shares().get(identifier)?;
+                Some((
+                    identifier,
+                    session_id,
+                    signature_share,
+                    &session.signing_package,
+                    verifying_share,
+                ))
+            })
+            .collect()
+    }
+
+    fn receive_batch_with_verification(
+        &mut self,
+        replies: Vec<(
+            Identifier<C>,
+            Option<SignatureShare<C>>,
+            SigningCommitments<C>,
+        )>,
+        mut verification_results: BTreeMap<(Identifier<C>, SessionId), bool>,
+    ) -> Vec<Result<SessionStatus<C>, Error<C>>> {
+        replies
+            .into_iter()
+            .map(|(identifier, signature_share, signing_commitments)| {
+                let verification_result = self
+                    .signer_session
+                    .get(&identifier)
+                    .and_then(|session_id| verification_results.remove(&(identifier, *session_id)));
+                self.receive_with_verification(
+                    identifier,
+                    signature_share,
+                    signing_commitments,
+                    verification_result,
+                )
+            })
+            .collect()
+    }
+
+    /// Same as [`Coordinator::receive`], but uses `verification_result` of
+    /// signature share if it was verified beforehand.
+    fn receive_with_verification(
+        &mut self,
+        identifier: Identifier<C>,
+        signature_share: Option<SignatureShare<C>>,
+        signing_commitments: SigningCommitments<C>,
+        verification_result: Option<bool>,
+    ) -> Result<SessionStatus<C>, Error<C>> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "Coordinator::receive",
+            identifier = ?identifier,
+            session_id = ?self.signer_session.get(&identifier)
+        )
+        .entered();
+
         if let Some(err) = self.malicious_signers.get(&identifier).copied() {
             return Err(Error::MaliciousSigner(err));
         }
 
+        if !self
+            .public_key_package
+            .verifying_shares()
+            .contains_key(&identifier)
+        {
+            return Err(Error::Frost(FrostError::UnknownIdentifier));
+        }
+
         if self.responsive_signers.contains(&identifier) {
             return Err(self.mark_malicious(identifier, MaliciousSignerError::UnsolicitedReply));
         }
@@ -128,29 +641,36 @@ impl<C: Ciphersuite> Coordinator<C> {
                 );
             };
 
-            let verification_result = (|| -> Result<(), FrostError<C>> {
-                let verifying_share = self
-                    .public_key_package
-                    .verifying_shares()
-                    .get(&identifier)
-                    .ok_or(FrostError::UnknownIdentifier)?;
-                let verifying_key = self.public_key_package.verifying_key();
+            let is_valid = verification_result.unwrap_or_else(|| {
+                (|| -> Result<(), FrostError<C>> {
+                    let verifying_share = self
+                        .public_key_package
+                        .verifying_shares()
+                        .get(&identifier)
+                        .ok_or(FrostError::UnknownIdentifier)?;
+                    let verifying_key = self.public_key_package.verifying_key();
 
-                frost_core::verify_signature_share(
-                    identifier,
-                    verifying_share,
-                    &signature_share,
-                    signing_package,
-                    verifying_key,
-                )
-            })();
+                    frost_core::verify_signature_share(
+                        identifier,
+                        verifying_share,
+                        &signature_share,
+                        signing_package,
+                        verifying_key,
+                    )
+                })()
+                .is_ok()
+            });
 
-            if verification_result.is_err() {
+            if !is_valid {
                 return Err(
                     self.mark_malicious(identifier, MaliciousSignerError::InvalidSignatureShare)
                 );
             }
 
+            #[cfg(feature = "tracing")]
+            tracing::debug!("signature share verified");
+
+            self.metrics.signature_share_verified();
             signature_shares.insert(identifier, signature_share);
 
             if signature_shares.len() == self.min_signers as usize {
@@ -159,12 +679,34 @@ impl<C: Ciphersuite> Coordinator<C> {
                     signature_shares,
                     &self.public_key_package,
                 )?;
+
+                #[cfg(feature = "tracing")]
+                tracing::info!("signature aggregated");
+
+                self.metrics.signature_aggregated(self.session_counter);
+
                 return Ok(SessionStatus::Finished { signature });
             }
         }
 
-        self.latest_signing_commitments
-            .insert(identifier, signing_commitments);
+        // Signer keeps its current signing commitments if the session used
+        // preprocessed ones, so they are sent again while still unused.
+        let is_unused = self
+            .signing_commitments
+            .get(&identifier)
+            .is_some_and(|unused| unused.contains(&signing_commitments));
+
+        // Signing commitments are registered only after the signature share is
+        // handled, so that a valid signature share is kept even if signing
+        // commitments of the same reply are rejected.
+        if !is_unused {
+            self.register_signing_commitments(identifier, &signing_commitments)?;
+
+            self.signing_commitments
+                .entry(identifier)
+                .or_default()
+                .push_back(signing_commitments);
+        }
         self.responsive_signers.insert(identifier);
 
         if self.responsive_signers.len() == self.min_signers as usize {
@@ -176,9 +718,9 @@ impl<C: Ciphersuite> Coordinator<C> {
                 .iter()
                 .copied()
                 .filter_map(|identifier| {
-                    self.latest_signing_commitments
-                        .get(&identifier)
-                        .copied()
+                    self.signing_commitments
+                        .get_mut(&identifier)
+                        .and_then(VecDeque::pop_front)
                         .map(|signing_commitments| (identifier, signing_commitments))
                 })
                 .collect();
@@ -197,6 +739,11 @@ impl<C: Ciphersuite> Coordinator<C> {
             );
 
             let signers = mem::take(&mut self.responsive_signers);
+            self.metrics.session_started(session_id);
+
+            #[cfg(feature = "tracing")]
+            tracing::info!(session_id, signers = ?signers, "session started");
+
             return Ok(SessionStatus::Started {
                 signers,
                 signing_package,
@@ -206,18 +753,193 @@ impl<C: Ciphersuite> Coordinator<C> {
         Ok(SessionStatus::InProgress)
     }
 
+    /// Returns the [`Metrics`] recorder.
+    pub fn metrics(&self) -> &M {
+        &self.metrics
+    }
+
+    /// Returns the maximum number of signers.
+    pub fn max_signers(&self) -> u16 {
+        self.max_signers
+    }
+
+    /// Returns the threshold number of signers.
+    pub fn min_signers(&self) -> u16 {
+        self.min_signers
+    }
+
+    /// Returns the message to sign.
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+
+    /// Returns identifier of coordinator run.
+    pub fn run_id(&self) -> RunId {
+        self.run_id
+    }
+
+    /// Returns the current [`SignerStatus`] of the signer.
+    pub fn signer_status(&self, identifier: &Identifier<C>) -> SignerStatus {
+        if let Some(err) = self.malicious_signers.get(identifier).copied() {
+            return SignerStatus::Malicious(err);
+        }
+
+        if self.responsive_signers.contains(identifier) {
+            return SignerStatus::Responsive;
+        }
+
+        match self.signer_session.get(identifier).copied() {
+            Some(session_id) => SignerStatus::InSession(session_id),
+            None => SignerStatus::Unknown,
+        }
+    }
+
+    /// Returns signers that replied to coordinator and wait for a new session.
+    pub fn responsive_signers(&self) -> &BTreeSet<Identifier<C>> {
+        &self.responsive_signers
+    }
+
+    /// Returns signers marked as malicious.
+    pub fn malicious_signers(&self) -> &BTreeMap<Identifier<C>, MaliciousSignerError> {
+        &self.malicious_signers
+    }
+
+    /// Returns the number of signers that can still be marked as malicious
+    /// before [`Error::TooManyMaliciousSigners`] is returned.
+    pub fn remaining_malicious_tolerance(&self) -> u16 {
+        (self.max_signers - self.min_signers).saturating_sub(self.malicious_signers.len() as u16)
+    }
+
+    /// Returns the number of sessions started so far.
+    pub fn session_count(&self) -> SessionId {
+        self.session_counter
+    }
+
+    /// Returns [`SessionInfo`] of the session.
+    pub fn session(&self, session_id: SessionId) -> Option<SessionInfo<'_, C>> {
+        self.session.get(&session_id).map(|session| SessionInfo {
+            session_id,
+            session,
+        })
+    }
+
+    /// Returns [`SessionInfo`] of all sessions that have not received
+    /// threshold number of [`SignatureShare`] yet.
+    pub fn open_sessions(&self) -> impl Iterator<Item = SessionInfo<'_, C>> {
+        self.session
+            .iter()
+            .filter(|(_, session)| session.signature_shares.len() < self.min_signers as usize)
+            .map(|(session_id, session)| SessionInfo {
+                session_id: *session_id,
+                session,
+            })
+    }
+
+    /// Receives a batch of preprocessed [`SigningCommitments`] from the signer
+    /// (see [`Signer::preprocess`](crate::Signer::preprocess)).
+    ///
+    /// The coordinator stores all [`SigningCommitments`] of the signer and uses
+    /// the oldest unused ones when the signer joins a new session, so sessions
+    /// can start without waiting for fresh [`SigningCommitments`]. Each of them
+    /// is used at most once.
+    ///
+    /// Returns [`Error::UnsignedEnvelope`] if identity keys of signers are
+    /// registered (see [`Coordinator::with_identity_keys`]).
+    pub fn preprocess(
+        &mut self,
+        identifier: Identifier<C>,
+        signing_commitments: Vec<SigningCommitments<C>>,
+    ) -> Result<(), Error<C>> {
+        if !self.identity_keys.is_empty() {
+            return Err(Error::UnsignedEnvelope);
+        }
+
+        if let Some(err) = self.malicious_signers.get(&identifier).copied() {
+            return Err(Error::MaliciousSigner(err));
+        }
+
+        if !self
+            .public_key_package
+            .verifying_shares()
+            .contains_key(&identifier)
+        {
+            return Err(Error::Frost(FrostError::UnknownIdentifier));
+        }
+
+        for signing_commitments in &signing_commitments {
+            self.register_signing_commitments(identifier, signing_commitments)?;
+        }
+
+        self.signing_commitments
+            .entry(identifier)
+            .or_default()
+            .extend(signing_commitments);
+
+        Ok(())
+    }
+
+    /// Remembers [`SigningCommitments`] received from the signer.
+    ///
+    /// Marks the signer as malicious if the same [`SigningCommitments`] were
+    /// already received from this signer or from another one. In the latter
+    /// case the later sender is blamed, which is sound as long as senders are
+    /// authenticated (see [`Coordinator::with_identity_keys`]) and
+    /// [`SigningCommitments`] cannot be seen in flight (e.g. they are sent
+    /// over an encrypted channel).
+    fn register_signing_commitments(
+        &mut self,
+        identifier: Identifier<C>,
+        signing_commitments: &SigningCommitments<C>,
+    ) -> Result<(), Error<C>> {
+        let serialization = Self::serialize_signing_commitments(signing_commitments)?;
+
+        match self
+            .received_signing_commitments
+            .get(&serialization)
+            .copied()
+        {
+            Some(sender) if sender == identifier => {
+                Err(self.mark_malicious(identifier, MaliciousSignerError::ReusedSigningCommitments))
+            }
+            Some(_) => {
+                Err(self.mark_malicious(identifier, MaliciousSignerError::CopiedSigningCommitments))
+            }
+            None => {
+                self.received_signing_commitments
+                    .insert(serialization, identifier);
+                Ok(())
+            }
+        }
+    }
+
+    fn serialize_signing_commitments(
+        signing_commitments: &SigningCommitments<C>,
+    ) -> Result<Vec<u8>, Error<C>> {
+        let mut serialization = signing_commitments.hiding().serialize()?;
+        serialization.extend_from_slice(&signing_commitments.binding().serialize()?);
+        Ok(serialization)
+    }
+
     /// Marks the signer as malicious with the given [`MaliciousSignerError`]
     /// and returns this error as [`Error::MaliciousSigner`].
     ///
     /// If the number of malicious signers exceeds the threshold, returns
     /// [`Error::TooManyMaliciousSigners`].
-    fn mark_malicious(
+    pub(crate) fn mark_malicious(
         &mut self,
         identifier: Identifier<C>,
         malicious_signer_error: MaliciousSignerError,
     ) -> Error<C> {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            identifier = ?identifier,
+            error = %malicious_signer_error,
+            "signer marked as malicious"
+        );
+
         self.malicious_signers
             .insert(identifier, malicious_signer_error);
+        self.metrics.signer_marked_malicious(malicious_signer_error);
 
         if self.malicious_signers.len() > (self.max_signers - self.min_signers) as usize {
             return Error::TooManyMaliciousSigners;
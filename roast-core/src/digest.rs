@@ -0,0 +1,80 @@
+use crate::{Error, MaliciousCoordinatorError};
+use alloc::{collections::BTreeMap, vec::Vec};
+use frost_core::{Ciphersuite, Identifier, SigningPackage};
+
+/// Represents digest of [`SigningPackage`].
+///
+/// Signers of the same session can exchange digests of received
+/// [`SigningPackage`] to detect a coordinator that sent different signing
+/// packages to different signers.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SigningPackageDigest(Vec<u8>);
+
+impl SigningPackageDigest {
+    /// Computes digest of the [`SigningPackage`].
+    pub fn new<C: Ciphersuite>(signing_package: &SigningPackage<C>) -> Result<Self, Error<C>> {
+        let message = signing_package.message();
+        let signing_commitments = signing_package.signing_commitments();
+
+        let mut preimage = Vec::new();
+        preimage.extend_from_slice(&(message.len() as u64).to_be_bytes());
+        preimage.extend_from_slice(message);
+        preimage.extend_from_slice(&(signing_commitments.len() as u64).to_be_bytes());
+
+        for (identifier, signing_commitments) in signing_commitments {
+            preimage.extend_from_slice(identifier.serialize().as_ref());
+            preimage.extend_from_slice(signing_commitments.hiding().serialize()?.as_ref());
+            preimage.extend_from_slice(signing_commitments.binding().serialize()?.as_ref());
+        }
+
+        Ok(Self(C::H5(&preimage).as_ref().to_vec()))
+    }
+}
+
+impl AsRef<[u8]> for SigningPackageDigest {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for SigningPackageDigest {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+/// Verifies that digests of [`SigningPackage`] received by other signers of
+/// the session match the digest of `signing_package` received by signer
+/// `identifier`.
+///
+/// Digests from signers that do not participate in the session are ignored.
+/// Returns [`MaliciousCoordinatorError::Equivocation`] if any digest does not
+/// match and [`MaliciousCoordinatorError::MissingSigningPackageDigest`] if a
+/// digest of any other signer of the session is missing.
+pub fn verify_signing_package_digests<C: Ciphersuite>(
+    identifier: &Identifier<C>,
+    signing_package: &SigningPackage<C>,
+    signing_package_digests: &BTreeMap<Identifier<C>, SigningPackageDigest>,
+) -> Result<(), Error<C>> {
+    let signing_package_digest = SigningPackageDigest::new(signing_package)?;
+
+    let digests: Vec<_> = signing_package
+        .signing_commitments()
+        .keys()
+        .filter(|other_identifier| *other_identifier != identifier)
+        .map(|other_identifier| signing_package_digests.get(other_identifier))
+        .collect();
+
+    if digests
+        .iter()
+        .flatten()
+        .any(|digest| **digest != signing_package_digest)
+    {
+        return Err(MaliciousCoordinatorError::Equivocation.into());
+    }
+    if digests.iter().any(Option::is_none) {
+        return Err(MaliciousCoordinatorError::MissingSigningPackageDigest.into());
+    }
+
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use crate::{CoordinatorEvent, SessionId, TimeoutReport};
+use alloc::vec::Vec;
 use frost_core::{Ciphersuite, Error as FrostError};
 #[cfg(feature = "std")]
 use thiserror::Error;
@@ -5,7 +7,8 @@ use thiserror::Error;
 use thiserror_nostd_notrait::Error;
 
 /// Represents all possible errors for which signer can be marked as malicious.
-#[derive(Error, Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Error, Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MaliciousSignerError {
     /// Signer unsolicitedly replied to coordinator.
     #[error("Unsolicited reply")]
@@ -13,18 +16,266 @@ pub enum MaliciousSignerError {
     /// Signature share is missing or its verification has failed.
     #[error("Invalid signature share")]
     InvalidSignatureShare,
+    /// Signer sent signing commitments that it has already sent before.
+    #[error("Reused signing commitments")]
+    ReusedSigningCommitments,
+    /// Signer sent signing commitments that another signer has already sent
+    /// before.
+    #[error("Copied signing commitments")]
+    CopiedSigningCommitments,
+    /// Signer has not replied to signing package within deadline.
+    #[error("Unresponsive")]
+    Unresponsive,
+}
+
+/// Represents all possible errors for which coordinator can be marked as
+/// malicious.
+#[derive(Error, Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MaliciousCoordinatorError {
+    /// Signing package does not contain signer's current signing commitments.
+    #[error("Missing signing commitments")]
+    MissingSigningCommitments,
+    /// Signing package contains signing commitments that differ from signer's
+    /// current signing commitments.
+    #[error("Invalid signing commitments")]
+    InvalidSigningCommitments,
+    /// Signing package does not contain threshold number of signing
+    /// commitments.
+    #[error("Invalid number of signing commitments")]
+    InvalidNumberOfSigningCommitments,
+    /// Signing package contains unexpected message.
+    #[error("Invalid message")]
+    InvalidMessage,
+    /// Coordinator sent different signing packages to signers of the same
+    /// session.
+    #[error("Equivocation")]
+    Equivocation,
+    /// Digest of signing package received by another signer of the session
+    /// is missing, so equivocation cannot be ruled out.
+    #[error("Missing signing package digest")]
+    MissingSigningPackageDigest,
+}
+
+/// Represents all possible errors of [`NonceStore`](crate::NonceStore).
+///
+/// The enum is non-exhaustive, since its variants depend on enabled features.
+#[derive(Error, Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum NonceStoreError {
+    /// I/O operation on the store failed with the given kind of error. The
+    /// error message is not kept, so that the error remains [`Copy`].
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[error("I/O error: {0}")]
+    Io(std::io::ErrorKind),
+    /// Stored signing nonces are malformed.
+    #[error("Malformed store")]
+    Malformed,
+    /// Other error of the store.
+    #[error("Other error")]
+    Other,
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for NonceStoreError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err.kind())
+    }
 }
 
 /// Represents all possible errors that can occur.
 #[derive(Error, Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "C: Ciphersuite"))]
 pub enum Error<C: Ciphersuite> {
     /// Error in FROST protocol.
     #[error("FROST error: {0}")]
-    Frost(#[from] FrostError<C>),
+    Frost(
+        #[from]
+        #[cfg_attr(feature = "serde", serde(with = "frost_error"))]
+        FrostError<C>,
+    ),
     /// Malicious signer.
     #[error("Malicious signer: {0}")]
     MaliciousSigner(#[from] MaliciousSignerError),
+    /// Malicious coordinator.
+    #[error("Malicious coordinator: {0}")]
+    MaliciousCoordinator(#[from] MaliciousCoordinatorError),
     /// Too many malicious signers.
     #[error("Too many malicious signers")]
     TooManyMaliciousSigners,
+    /// Nonce store failed to load or persist signing nonces.
+    #[error("Nonce store error: {0}")]
+    NonceStore(#[from] NonceStoreError),
+    /// Transcript is malformed.
+    #[error("Invalid transcript")]
+    InvalidTranscript,
+    /// Replayed transcript entry produced a different output.
+    #[error("Transcript mismatch at entry {0}")]
+    TranscriptMismatch(usize),
+    /// Message is malformed.
+    #[error("Malformed message")]
+    MalformedMessage,
+    /// Buffer is too small to encode message.
+    #[error("Buffer too small")]
+    BufferTooSmall,
+    /// Message uses unsupported protocol version.
+    #[error("Unsupported protocol version {0}")]
+    UnsupportedProtocolVersion(u16),
+    /// Message belongs to another ciphersuite.
+    #[error("Ciphersuite mismatch")]
+    CiphersuiteMismatch,
+    /// Envelope is not signed with long-term identity key of its sender.
+    #[error("Invalid envelope signature")]
+    InvalidEnvelopeSignature,
+    /// No long-term identity key is registered for the sender.
+    #[error("Missing identity key")]
+    MissingIdentityKey,
+    /// Reply must be wrapped in [`SignedEnvelope`](crate::SignedEnvelope), since
+    /// long-term identity keys are registered.
+    #[error("Unsigned envelope")]
+    UnsignedEnvelope,
+    /// Message belongs to another coordinator run.
+    #[error("Run id mismatch")]
+    RunIdMismatch,
+    /// Message belongs to another session.
+    #[error("Session id mismatch: got session {0}")]
+    SessionIdMismatch(SessionId),
+    /// Envelope carries signing commitments that were already received from
+    /// its sender.
+    #[error("Replayed envelope")]
+    ReplayedEnvelope,
+    /// Several coordinators use the same run id.
+    #[error("Duplicate run id")]
+    DuplicateRunId,
+}
+
+/// Represents error of [`Coordinator::receive_many`](crate::Coordinator::receive_many)
+/// with events of the whole batch.
+#[derive(Error, Debug)]
+#[error("{error}")]
+pub struct ReceiveManyError<C: Ciphersuite> {
+    /// Events of the whole batch, since its processing does not stop at the
+    /// error.
+    pub events: Vec<CoordinatorEvent<C>>,
+    /// The first error other than [`Error::MaliciousSigner`].
+    pub error: Error<C>,
+}
+
+/// Represents error of [`LivenessCoordinator::tick`](crate::LivenessCoordinator::tick)
+/// with reports about all signers whose deadline has passed.
+#[derive(Error, Debug)]
+#[error("{error}")]
+pub struct TickError<C: Ciphersuite> {
+    /// Reports about all signers whose deadline has passed, since all of them
+    /// are marked as malicious.
+    pub reports: Vec<TimeoutReport<C>>,
+    /// [`Error::TooManyMaliciousSigners`].
+    pub error: Error<C>,
+}
+
+// Only errors of FROST protocol that `Coordinator` can return are supported.
+#[cfg(feature = "serde")]
+mod frost_error {
+    use frost_core::{Ciphersuite, Error as FrostError, Identifier};
+    use serde::{ser, Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(bound = "C: Ciphersuite")]
+    enum Repr<C: Ciphersuite> {
+        InvalidMinSigners,
+        InvalidMaxSigners,
+        MalformedIdentifier,
+        DuplicatedIdentifier,
+        UnknownIdentifier,
+        MalformedSignature,
+        InvalidSignature,
+        IdentityCommitment,
+        MissingCommitment,
+        IncorrectCommitment,
+        IncorrectNumberOfCommitments,
+        InvalidSignatureShare { culprit: Identifier<C> },
+        SerializationError,
+        DeserializationError,
+    }
+
+    pub(super) fn serialize<C, S>(err: &FrostError<C>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        C: Ciphersuite,
+        S: Serializer,
+    {
+        let repr = match *err {
+            FrostError::InvalidMinSigners => Repr::InvalidMinSigners,
+            FrostError::InvalidMaxSigners => Repr::InvalidMaxSigners,
+            FrostError::MalformedIdentifier => Repr::MalformedIdentifier,
+            FrostError::DuplicatedIdentifier => Repr::DuplicatedIdentifier,
+            FrostError::UnknownIdentifier => Repr::UnknownIdentifier,
+            FrostError::MalformedSignature => Repr::MalformedSignature,
+            FrostError::InvalidSignature => Repr::InvalidSignature,
+            FrostError::IdentityCommitment => Repr::IdentityCommitment,
+            FrostError::MissingCommitment => Repr::MissingCommitment,
+            FrostError::IncorrectCommitment => Repr::IncorrectCommitment,
+            FrostError::IncorrectNumberOfCommitments => Repr::IncorrectNumberOfCommitments,
+            FrostError::InvalidSignatureShare { culprit } => {
+                Repr::InvalidSignatureShare { culprit }
+            }
+            FrostError::SerializationError => Repr::SerializationError,
+            FrostError::DeserializationError => Repr::DeserializationError,
+            _ => return Err(ser::Error::custom("unsupported FROST error")),
+        };
+
+        repr.serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, C, D>(deserializer: D) -> Result<FrostError<C>, D::Error>
+    where
+        C: Ciphersuite,
+        D: Deserializer<'de>,
+    {
+        Ok(match Repr::<C>::deserialize(deserializer)? {
+            Repr::InvalidMinSigners => FrostError::InvalidMinSigners,
+            Repr::InvalidMaxSigners => FrostError::InvalidMaxSigners,
+            Repr::MalformedIdentifier => FrostError::MalformedIdentifier,
+            Repr::DuplicatedIdentifier => FrostError::DuplicatedIdentifier,
+            Repr::UnknownIdentifier => FrostError::UnknownIdentifier,
+            Repr::MalformedSignature => FrostError::MalformedSignature,
+            Repr::InvalidSignature => FrostError::InvalidSignature,
+            Repr::IdentityCommitment => FrostError::IdentityCommitment,
+            Repr::MissingCommitment => FrostError::MissingCommitment,
+            Repr::IncorrectCommitment => FrostError::IncorrectCommitment,
+            Repr::IncorrectNumberOfCommitments => FrostError::IncorrectNumberOfCommitments,
+            Repr::InvalidSignatureShare { culprit } => {
+                FrostError::InvalidSignatureShare { culprit }
+            }
+            Repr::SerializationError => FrostError::SerializationError,
+            Repr::DeserializationError => FrostError::DeserializationError,
+        })
+    }
+}
+
+/// Represents all possible errors of [`SecureChannel`](crate::SecureChannel).
+#[cfg(feature = "noise")]
+#[derive(Error, Debug)]
+pub enum ChannelError {
+    /// I/O error of the underlying stream.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// Error in Noise protocol, e.g. failed handshake or decryption.
+    #[error("Noise error: {0}")]
+    Noise(#[from] snow::Error),
+    /// Other endpoint uses static key that is not registered.
+    #[error("Unknown peer")]
+    UnknownPeer,
+    /// Received message is not a reply of the signer that owns static key of
+    /// the other endpoint.
+    #[error("Sender mismatch")]
+    SenderMismatch,
+    /// Message exceeds maximum length.
+    #[error("Message too large")]
+    MessageTooLarge,
+    /// Message is malformed.
+    #[error("Malformed message")]
+    MalformedMessage,
 }
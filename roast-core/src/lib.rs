@@ -6,15 +6,40 @@
 #[cfg_attr(any(test, feature = "test-impl"), macro_use)]
 extern crate alloc;
 
+#[cfg(feature = "noise")]
+mod channel;
+#[cfg(feature = "serialization")]
+mod codec;
+mod compact;
 mod coordinator;
+mod digest;
 mod error;
+mod liveness;
+mod message;
+mod metrics;
+mod multi;
+mod nonce_store;
 mod signer;
+mod transcript;
 
+#[cfg(feature = "bench-impl")]
+pub mod benches;
+#[cfg(feature = "protobuf")]
+pub mod protobuf;
 #[cfg(any(test, feature = "test-impl"))]
 pub mod tests;
 
 pub use frost_core as frost;
 
+#[cfg(feature = "noise")]
+pub use channel::*;
 pub use coordinator::*;
+pub use digest::*;
 pub use error::*;
+pub use liveness::*;
+pub use message::*;
+pub use metrics::*;
+pub use multi::*;
+pub use nonce_store::*;
 pub use signer::*;
+pub use transcript::*;
@@ -0,0 +1,234 @@
+use crate::{
+    Coordinator, Envelope, Error, MaliciousSignerError, Metrics, Payload, SessionStatus,
+    SignedEnvelope, TickError,
+};
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::time::Duration;
+use frost_core::{
+    keys::VerifyingShare, round1::SigningCommitments, round2::SignatureShare, Ciphersuite,
+    Identifier, SigningPackage, VerifyingKey,
+};
+
+/// Represents all possible policies for signers that have not replied to
+/// [`SigningPackage`](frost_core::SigningPackage) within deadline.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TimeoutPolicy {
+    /// Only report such signers.
+    Report,
+    /// Report such signers and mark them as malicious with
+    /// [`MaliciousSignerError::Unresponsive`].
+    MarkUnresponsive,
+}
+
+/// Represents signer that has not replied to
+/// [`SigningPackage`](frost_core::SigningPackage) within deadline.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct TimeoutReport<C: Ciphersuite> {
+    /// Identifier of the signer.
+    pub identifier: Identifier<C>,
+    /// Time by which the signer had to reply.
+    pub deadline: Duration,
+}
+
+/// Represents coordinator that tracks liveness of signers.
+///
+/// Time is passed explicitly as [`Duration`] since an arbitrary epoch, so any
+/// clock (including a fake one in tests) can be used.
+#[derive(Debug)]
+pub struct LivenessCoordinator<C: Ciphersuite, M: Metrics = ()> {
+    coordinator: Coordinator<C, M>,
+    timeout: Duration,
+    policy: TimeoutPolicy,
+    deadlines: BTreeMap<Identifier<C>, Duration>,
+}
+
+impl<C: Ciphersuite, M: Metrics> LivenessCoordinator<C, M> {
+    /// Creates a new [`LivenessCoordinator`] that expects signers to reply to
+    /// [`SigningPackage`](frost_core::SigningPackage) within `timeout` and
+    /// applies `policy` to those who do not.
+    pub fn new(coordinator: Coordinator<C, M>, timeout: Duration, policy: TimeoutPolicy) -> Self {
+        Self {
+            coordinator,
+            timeout,
+            policy,
+            deadlines: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the underlying [`Coordinator`].
+    pub fn coordinator(&self) -> &Coordinator<C, M> {
+        &self.coordinator
+    }
+
+    /// Same as [`Coordinator::receive`], but also starts deadlines for signers
+    /// of started session at `now`.
+    ///
+    /// Deadline of the signer is kept if its reply is rejected without marking
+    /// it as malicious, so that it is still reported by
+    /// [`LivenessCoordinator::tick`]. If identity keys of signers are
+    /// registered, use [`LivenessCoordinator::receive_signed_envelope`]
+    /// instead.
+    pub fn receive(
+        &mut self,
+        now: Duration,
+        identifier: Identifier<C>,
+        signature_share: Option<SignatureShare<C>>,
+        signing_commitments: SigningCommitments<C>,
+    ) -> Result<SessionStatus<C>, Error<C>> {
+        let result = self
+            .coordinator
+            .receive(identifier, signature_share, signing_commitments);
+
+        self.update_deadlines(now, identifier, result)
+    }
+
+    /// Same as [`Coordinator::receive_envelope`], but also starts deadlines
+    /// for signers of started session at `now` as in
+    /// [`LivenessCoordinator::receive`].
+    pub fn receive_envelope(
+        &mut self,
+        now: Duration,
+        envelope: Envelope<C>,
+    ) -> Result<SessionStatus<C>, Error<C>> {
+        let Payload::Reply { identifier, .. } = *envelope.payload() else {
+            return Err(Error::MalformedMessage);
+        };
+
+        let result = self.coordinator.receive_envelope(envelope);
+
+        self.update_deadlines(now, identifier, result)
+    }
+
+    /// Same as [`Coordinator::receive_signed_envelope`], but also starts
+    /// deadlines for signers of started session at `now` as in
+    /// [`LivenessCoordinator::receive`].
+    pub fn receive_signed_envelope(
+        &mut self,
+        now: Duration,
+        signed_envelope: SignedEnvelope<C>,
+    ) -> Result<SessionStatus<C>, Error<C>> {
+        let Payload::Reply { identifier, .. } = *signed_envelope.envelope().payload() else {
+            return Err(Error::MalformedMessage);
+        };
+
+        let result = self.coordinator.receive_signed_envelope(signed_envelope);
+
+        self.update_deadlines(now, identifier, result)
+    }
+
+    /// Same as [`Coordinator::receive_batch`], but also starts deadlines for
+    /// signers of started sessions at `now` as in
+    /// [`LivenessCoordinator::receive`].
+    pub fn receive_batch(
+        &mut self,
+        now: Duration,
+        replies: Vec<(
+            Identifier<C>,
+            Option<SignatureShare<C>>,
+            SigningCommitments<C>,
+        )>,
+    ) -> Vec<Result<SessionStatus<C>, Error<C>>>
+    where
+        Identifier<C>: Sync,
+        SignatureShare<C>: Sync,
+        SigningPackage<C>: Sync,
+        VerifyingShare<C>: Sync,
+        VerifyingKey<C>: Sync,
+    {
+        let identifiers: Vec<_> = replies.iter().map(|(identifier, ..)| *identifier).collect();
+        let results = self.coordinator.receive_batch(replies);
+
+        identifiers
+            .into_iter()
+            .zip(results)
+            .map(|(identifier, result)| self.update_deadlines(now, identifier, result))
+            .collect()
+    }
+
+    /// Same as [`Coordinator::preprocess`], but also drops deadline of the
+    /// signer if it is marked as malicious.
+    pub fn preprocess(
+        &mut self,
+        identifier: Identifier<C>,
+        signing_commitments: Vec<SigningCommitments<C>>,
+    ) -> Result<(), Error<C>> {
+        let result = self.coordinator.preprocess(identifier, signing_commitments);
+
+        if self
+            .coordinator
+            .malicious_signers()
+            .contains_key(&identifier)
+        {
+            self.deadlines.remove(&identifier);
+        }
+
+        result
+    }
+
+    fn update_deadlines(
+        &mut self,
+        now: Duration,
+        identifier: Identifier<C>,
+        result: Result<SessionStatus<C>, Error<C>>,
+    ) -> Result<SessionStatus<C>, Error<C>> {
+        if result.is_ok()
+            || self
+                .coordinator
+                .malicious_signers()
+                .contains_key(&identifier)
+        {
+            self.deadlines.remove(&identifier);
+        }
+
+        let session_status = result?;
+
+        if let SessionStatus::Started { signers, .. } = &session_status {
+            let deadline = now.saturating_add(self.timeout);
+            for identifier in signers.iter().copied() {
+                self.deadlines.insert(identifier, deadline);
+            }
+        }
+
+        Ok(session_status)
+    }
+
+    /// Returns reports about signers whose deadline has passed at `now`.
+    ///
+    /// Each signer is reported once per session. With
+    /// [`TimeoutPolicy::MarkUnresponsive`] reported signers are also marked as
+    /// malicious, and [`Error::TooManyMaliciousSigners`] is returned together
+    /// with all reports if there are too many of them.
+    pub fn tick(&mut self, now: Duration) -> Result<Vec<TimeoutReport<C>>, TickError<C>> {
+        let timeout_reports: Vec<_> = self
+            .deadlines
+            .iter()
+            .filter(|(_, deadline)| **deadline <= now)
+            .map(|(identifier, deadline)| TimeoutReport {
+                identifier: *identifier,
+                deadline: *deadline,
+            })
+            .collect();
+
+        let mut error = None;
+        for timeout_report in &timeout_reports {
+            self.deadlines.remove(&timeout_report.identifier);
+
+            if self.policy == TimeoutPolicy::MarkUnresponsive {
+                if let Error::TooManyMaliciousSigners = self.coordinator.mark_malicious(
+                    timeout_report.identifier,
+                    MaliciousSignerError::Unresponsive,
+                ) {
+                    error = Some(Error::TooManyMaliciousSigners);
+                }
+            }
+        }
+
+        match error {
+            Some(error) => Err(TickError {
+                reports: timeout_reports,
+                error,
+            }),
+            None => Ok(timeout_reports),
+        }
+    }
+}
@@ -0,0 +1,311 @@
+use crate::{Error, SessionId};
+use alloc::vec::Vec;
+use frost_core::{
+    round1::SigningCommitments, round2::SignatureShare, Ciphersuite, Identifier, Signature,
+    SigningKey, SigningPackage, VerifyingKey,
+};
+use rand_core::{CryptoRng, RngCore};
+
+/// Represents version of wire protocol implemented by this crate.
+pub const PROTOCOL_VERSION: u16 = 1;
+
+/// Represents identifier of coordinator run.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunId([u8; 32]);
+
+impl RunId {
+    /// Creates a new [`RunId`] from bytes.
+    pub fn new(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Derives [`RunId`] from verifying key of the group and message to sign,
+    /// so that runs signing different messages with the same key get
+    /// different run ids.
+    ///
+    /// Runs that sign the same message with the same key should use
+    /// [`RunId::new`] with unique (e.g. random) bytes instead. Returns error if
+    /// verifying key cannot be serialized.
+    pub fn derive<C: Ciphersuite>(
+        verifying_key: &VerifyingKey<C>,
+        message: &[u8],
+    ) -> Result<Self, Error<C>> {
+        let mut input = b"ROAST run id".to_vec();
+        input.extend_from_slice(&verifying_key.serialize()?);
+        input.extend_from_slice(message);
+
+        let mut bytes = [0; 32];
+        bytes.copy_from_slice(&C::H4(&input).as_ref()[..32]);
+        Ok(Self(bytes))
+    }
+
+    /// Returns bytes of [`RunId`].
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+impl AsRef<[u8]> for RunId {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<[u8; 32]> for RunId {
+    fn from(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+}
+
+/// Represents all possible payloads exchanged between coordinator and signers.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "C: Ciphersuite"))]
+pub enum Payload<C: Ciphersuite> {
+    /// Reply of signer to coordinator.
+    Reply {
+        /// Identifier of the signer.
+        identifier: Identifier<C>,
+        /// Signature share for signing package of the session, if any.
+        signature_share: Option<SignatureShare<C>>,
+        /// Signing commitments for the next session.
+        signing_commitments: SigningCommitments<C>,
+    },
+    /// Signing package sent by coordinator to signers of the session.
+    SigningPackage(SigningPackage<C>),
+}
+
+/// Represents versioned message that binds [`Payload`] to protocol version,
+/// ciphersuite, coordinator run and session.
+///
+/// Signers reply with session id of the signing package they reply to, or `0`
+/// if they have not received any signing package yet.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "C: Ciphersuite"))]
+pub struct Envelope<C: Ciphersuite> {
+    run_id: RunId,
+    session_id: SessionId,
+    payload: Payload<C>,
+}
+
+impl<C: Ciphersuite> Envelope<C> {
+    /// Creates a new [`Envelope`].
+    pub fn new(run_id: RunId, session_id: SessionId, payload: Payload<C>) -> Self {
+        Self {
+            run_id,
+            session_id,
+            payload,
+        }
+    }
+
+    /// Returns identifier of coordinator run.
+    pub fn run_id(&self) -> RunId {
+        self.run_id
+    }
+
+    /// Returns identifier of the session.
+    pub fn session_id(&self) -> SessionId {
+        self.session_id
+    }
+
+    /// Returns payload of the message.
+    pub fn payload(&self) -> &Payload<C> {
+        &self.payload
+    }
+
+    /// Consumes [`Envelope`] and returns its payload.
+    pub fn into_payload(self) -> Payload<C> {
+        self.payload
+    }
+
+    /// Checks that message belongs to coordinator run `run_id` and session
+    /// `session_id`.
+    pub fn verify(&self, run_id: RunId, session_id: SessionId) -> Result<(), Error<C>> {
+        if self.run_id != run_id {
+            return Err(Error::RunIdMismatch);
+        }
+        if self.session_id != session_id {
+            return Err(Error::SessionIdMismatch(self.session_id));
+        }
+        Ok(())
+    }
+}
+
+/// Represents [`Envelope`] signed with long-term identity key of its sender.
+///
+/// Signature covers compact encoding of the envelope (see
+/// [`Envelope::to_compact`]), so it also binds protocol version, ciphersuite,
+/// run id and session id.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "C: Ciphersuite"))]
+pub struct SignedEnvelope<C: Ciphersuite> {
+    envelope: Envelope<C>,
+    signature: Signature<C>,
+}
+
+impl<C: Ciphersuite> SignedEnvelope<C> {
+    /// Signs `envelope` with long-term identity key `signing_key`.
+    pub fn sign<RNG: RngCore + CryptoRng>(
+        envelope: Envelope<C>,
+        signing_key: &SigningKey<C>,
+        rng: &mut RNG,
+    ) -> Result<Self, Error<C>> {
+        let signature = signing_key.sign(rng, &Self::signing_message(&envelope)?);
+        Ok(Self {
+            envelope,
+            signature,
+        })
+    }
+
+    /// Creates a new [`SignedEnvelope`] from `envelope` and its `signature`.
+    pub fn new(envelope: Envelope<C>, signature: Signature<C>) -> Self {
+        Self {
+            envelope,
+            signature,
+        }
+    }
+
+    /// Verifies that the envelope is signed with long-term identity key
+    /// `verifying_key`.
+    ///
+    /// Returns [`Error::InvalidEnvelopeSignature`] otherwise.
+    pub fn verify(&self, verifying_key: &VerifyingKey<C>) -> Result<(), Error<C>> {
+        verifying_key
+            .verify(&Self::signing_message(&self.envelope)?, &self.signature)
+            .map_err(|_| Error::InvalidEnvelopeSignature)
+    }
+
+    /// Returns the signed envelope.
+    pub fn envelope(&self) -> &Envelope<C> {
+        &self.envelope
+    }
+
+    /// Returns signature of the envelope.
+    pub fn signature(&self) -> &Signature<C> {
+        &self.signature
+    }
+
+    /// Consumes [`SignedEnvelope`] and returns the signed envelope without
+    /// verifying its signature.
+    pub fn into_envelope(self) -> Envelope<C> {
+        self.envelope
+    }
+
+    fn signing_message(envelope: &Envelope<C>) -> Result<Vec<u8>, Error<C>> {
+        let mut message = b"ROAST signed envelope".to_vec();
+        message.extend_from_slice(&envelope.to_compact()?);
+        Ok(message)
+    }
+}
+
+#[cfg(feature = "serialization")]
+mod serialization {
+    use super::{Envelope, Payload, RunId, PROTOCOL_VERSION};
+    use crate::{
+        codec::{Reader, Writer},
+        Error,
+    };
+    use alloc::vec::Vec;
+    use frost_core::{
+        round1::SigningCommitments, round2::SignatureShare, Ciphersuite, Identifier, SigningPackage,
+    };
+
+    const REPLY: u8 = 0;
+    const SIGNING_PACKAGE: u8 = 1;
+
+    impl<C: Ciphersuite> Envelope<C> {
+        /// Serializes [`Envelope`] to bytes.
+        ///
+        /// Encoding starts with [`PROTOCOL_VERSION`] as big-endian `u16` and
+        /// ciphersuite ID, followed by run id, session id and payload. Every
+        /// variable-length field is prefixed with its length as big-endian
+        /// `u32`.
+        pub fn serialize(&self) -> Result<Vec<u8>, Error<C>> {
+            let mut writer = Writer::new(Error::MalformedMessage);
+
+            writer.u16(PROTOCOL_VERSION);
+            writer.bytes(C::ID.as_bytes())?;
+            writer.array(self.run_id.as_ref());
+            writer.u16(self.session_id);
+
+            match &self.payload {
+                Payload::Reply {
+                    identifier,
+                    signature_share,
+                    signing_commitments,
+                } => {
+                    writer.u8(REPLY);
+                    writer.bytes(identifier.serialize().as_ref())?;
+                    match signature_share {
+                        Some(signature_share) => {
+                            writer.u8(1);
+                            writer.bytes(signature_share.serialize().as_ref())?;
+                        }
+                        None => writer.u8(0),
+                    }
+                    writer.bytes(&signing_commitments.serialize()?)?;
+                }
+                Payload::SigningPackage(signing_package) => {
+                    writer.u8(SIGNING_PACKAGE);
+                    writer.bytes(&signing_package.serialize()?)?;
+                }
+            }
+
+            Ok(writer.into_bytes())
+        }
+
+        /// Deserializes [`Envelope`] from bytes produced by
+        /// [`Envelope::serialize`].
+        ///
+        /// Rejects messages of other protocol versions with
+        /// [`Error::UnsupportedProtocolVersion`] and messages of other
+        /// ciphersuites with [`Error::CiphersuiteMismatch`].
+        pub fn deserialize(bytes: &[u8]) -> Result<Self, Error<C>> {
+            let mut reader = Reader::new(bytes, Error::MalformedMessage);
+
+            let version = reader.u16()?;
+            if version != PROTOCOL_VERSION {
+                return Err(Error::UnsupportedProtocolVersion(version));
+            }
+            if reader.bytes()? != C::ID.as_bytes() {
+                return Err(Error::CiphersuiteMismatch);
+            }
+
+            let run_id = RunId::new(reader.array()?);
+            let session_id = reader.u16()?;
+
+            let payload = match reader.u8()? {
+                REPLY => {
+                    let identifier = Identifier::deserialize(reader.bytes()?)?;
+                    let signature_share = match reader.u8()? {
+                        0 => None,
+                        1 => Some(SignatureShare::deserialize(reader.bytes()?)?),
+                        _ => return Err(Error::MalformedMessage),
+                    };
+                    let signing_commitments = SigningCommitments::deserialize(reader.bytes()?)?;
+
+                    Payload::Reply {
+                        identifier,
+                        signature_share,
+                        signing_commitments,
+                    }
+                }
+                SIGNING_PACKAGE => {
+                    Payload::SigningPackage(SigningPackage::deserialize(reader.bytes()?)?)
+                }
+                _ => return Err(Error::MalformedMessage),
+            };
+
+            reader.finish()?;
+
+            Ok(Self {
+                run_id,
+                session_id,
+                payload,
+            })
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{MaliciousSignerError, SessionId};
+
+/// Represents recorder of [`Coordinator`](crate::Coordinator) metrics.
+///
+/// All methods have empty default implementations, so implementors can
+/// record only metrics they are interested in.
+pub trait Metrics {
+    /// Called when coordinator is created.
+    fn run_started(&mut self) {}
+
+    /// Called when session is started.
+    fn session_started(&mut self, _session_id: SessionId) {}
+
+    /// Called when signature share is verified.
+    fn signature_share_verified(&mut self) {}
+
+    /// Called when signer is marked as malicious.
+    fn signer_marked_malicious(&mut self, _malicious_signer_error: MaliciousSignerError) {}
+
+    /// Called when final signature is aggregated after `sessions` sessions
+    /// were started.
+    fn signature_aggregated(&mut self, _sessions: SessionId) {}
+}
+
+impl Metrics for () {}
+
+impl<M: Metrics + ?Sized> Metrics for &mut M {
+    fn run_started(&mut self) {
+        (**self).run_started()
+    }
+
+    fn session_started(&mut self, session_id: SessionId) {
+        (**self).session_started(session_id)
+    }
+
+    fn signature_share_verified(&mut self) {
+        (**self).signature_share_verified()
+    }
+
+    fn signer_marked_malicious(&mut self, malicious_signer_error: MaliciousSignerError) {
+        (**self).signer_marked_malicious(malicious_signer_error)
+    }
+
+    fn signature_aggregated(&mut self, sessions: SessionId) {
+        (**self).signature_aggregated(sessions)
+    }
+}
+
+#[cfg(feature = "std")]
+mod in_memory {
+    use super::Metrics;
+    use crate::{MaliciousSignerError, SessionId};
+    use std::{
+        collections::BTreeMap,
+        time::{Duration, Instant},
+    };
+
+    /// Represents in-memory [`Metrics`] recorder.
+    ///
+    /// Can be shared between several runs by passing `&mut InMemoryMetrics` to
+    /// [`Coordinator::with_metrics`](crate::Coordinator::with_metrics).
+    #[derive(Debug, Default, Clone)]
+    pub struct InMemoryMetrics {
+        started_at: Option<Instant>,
+        sessions_started: u64,
+        signature_shares_verified: u64,
+        malicious_signers: BTreeMap<MaliciousSignerError, u64>,
+        time_to_signature: Vec<Duration>,
+        sessions_per_run: Vec<SessionId>,
+    }
+
+    impl InMemoryMetrics {
+        /// Creates a new empty [`InMemoryMetrics`].
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Returns the number of started sessions.
+        pub fn sessions_started(&self) -> u64 {
+            self.sessions_started
+        }
+
+        /// Returns the number of verified signature shares.
+        pub fn signature_shares_verified(&self) -> u64 {
+            self.signature_shares_verified
+        }
+
+        /// Returns the number of signers marked as malicious by
+        /// [`MaliciousSignerError`].
+        pub fn malicious_signers(&self) -> &BTreeMap<MaliciousSignerError, u64> {
+            &self.malicious_signers
+        }
+
+        /// Returns time from coordinator creation to final signature for each
+        /// finished run.
+        pub fn time_to_signature(&self) -> &[Duration] {
+            &self.time_to_signature
+        }
+
+        /// Returns the number of sessions started for each finished run.
+        pub fn sessions_per_run(&self) -> &[SessionId] {
+            &self.sessions_per_run
+        }
+    }
+
+    impl Metrics for InMemoryMetrics {
+        fn run_started(&mut self) {
+            self.started_at = Some(Instant::now());
+        }
+
+        fn session_started(&mut self, _session_id: SessionId) {
+            self.sessions_started += 1;
+        }
+
+        fn signature_share_verified(&mut self) {
+            self.signature_shares_verified += 1;
+        }
+
+        fn signer_marked_malicious(&mut self, malicious_signer_error: MaliciousSignerError) {
+            *self
+                .malicious_signers
+                .entry(malicious_signer_error)
+                .or_default() += 1;
+        }
+
+        fn signature_aggregated(&mut self, sessions: SessionId) {
+            if let Some(started_at) = self.started_at.take() {
+                self.time_to_signature.push(started_at.elapsed());
+            }
+            self.sessions_per_run.push(sessions);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub use in_memory::*;
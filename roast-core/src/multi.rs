@@ -0,0 +1,274 @@
+use crate::{
+    Coordinator, Envelope, Error, MemoryNonceStore, Metrics, NonceStore, RunId, SessionId,
+    SessionStatus, SignedEnvelope, Signer,
+};
+use alloc::{collections::BTreeMap, vec::Vec};
+use frost_core::{
+    keys::{KeyPackage, PublicKeyPackage},
+    Ciphersuite, Identifier, Signature, VerifyingKey,
+};
+use rand_core::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+/// Represents signer that takes part in runs of several coordinators signing
+/// the same message, so that a faulty coordinator can be tolerated.
+///
+/// Each run has its own [`Signer`] with independent
+/// [`SigningNonces`](frost_core::round1::SigningNonces), so
+/// [`SigningCommitments`](frost_core::round1::SigningCommitments) sent to one
+/// coordinator can never be used by another one.
+#[derive(Debug)]
+pub struct MultiSigner<C: Ciphersuite, S: NonceStore<C> = MemoryNonceStore<C>> {
+    signers: BTreeMap<RunId, Signer<C, S>>,
+}
+
+impl<C: Ciphersuite> MultiSigner<C> {
+    /// Creates a new [`MultiSigner`] that expects to sign `message` in runs of
+    /// coordinators with `run_ids`.
+    ///
+    /// Returns [`Error::DuplicateRunId`] if `run_ids` are not distinct.
+    pub fn new<RNG: RngCore + CryptoRng>(
+        key_package: KeyPackage<C>,
+        message: Vec<u8>,
+        run_ids: impl IntoIterator<Item = RunId>,
+        rng: &mut RNG,
+    ) -> Result<Self, Error<C>> {
+        let nonce_stores = run_ids
+            .into_iter()
+            .map(|run_id| (run_id, MemoryNonceStore::new()));
+        Self::with_nonce_stores(key_package, message, nonce_stores, rng)
+    }
+}
+
+impl<C: Ciphersuite, S: NonceStore<C>> MultiSigner<C, S> {
+    /// Creates a new [`MultiSigner`] that expects to sign `message` in runs of
+    /// coordinators with the given run ids and keeps unused
+    /// [`SigningNonces`](frost_core::round1::SigningNonces) of each run in its
+    /// own nonce store (see [`Signer::with_nonce_store`]).
+    ///
+    /// Each [`Signer`] gets its own copy of `key_package`, and `key_package`
+    /// itself is zeroized.
+    ///
+    /// Returns [`Error::DuplicateRunId`] if run ids are not distinct.
+    pub fn with_nonce_stores<RNG: RngCore + CryptoRng>(
+        mut key_package: KeyPackage<C>,
+        message: Vec<u8>,
+        nonce_stores: impl IntoIterator<Item = (RunId, S)>,
+        rng: &mut RNG,
+    ) -> Result<Self, Error<C>> {
+        let mut signers = BTreeMap::new();
+        let result = (|| -> Result<(), Error<C>> {
+            for (run_id, nonce_store) in nonce_stores {
+                if signers.contains_key(&run_id) {
+                    return Err(Error::DuplicateRunId);
+                }
+                let signer = Signer::with_nonce_store(
+                    key_package.clone(),
+                    message.clone(),
+                    nonce_store,
+                    rng,
+                )?;
+                signers.insert(run_id, signer.with_run_id(run_id));
+            }
+            Ok(())
+        })();
+        key_package.zeroize();
+        result?;
+
+        Ok(Self { signers })
+    }
+
+    /// Returns [`Signer`] of the run `run_id`.
+    pub fn signer(&self, run_id: RunId) -> Option<&Signer<C, S>> {
+        self.signers.get(&run_id)
+    }
+
+    /// Returns mutable [`Signer`] of the run `run_id`, e.g. to preprocess
+    /// [`SigningCommitments`](frost_core::round1::SigningCommitments) (see
+    /// [`Signer::preprocess`]).
+    pub fn signer_mut(&mut self, run_id: RunId) -> Option<&mut Signer<C, S>> {
+        self.signers.get_mut(&run_id)
+    }
+
+    /// Returns the first replies of this signer to all coordinators (see
+    /// [`Signer::envelope`]).
+    pub fn envelopes(&self) -> Vec<Envelope<C>> {
+        self.signers.values().map(Signer::envelope).collect()
+    }
+
+    /// Same as [`Signer::receive_envelope`] with [`Signer`] of the run the
+    /// message belongs to.
+    ///
+    /// Returns [`Error::RunIdMismatch`] if the message belongs to none of the
+    /// runs.
+    pub fn receive_envelope<RNG: RngCore + CryptoRng>(
+        &mut self,
+        envelope: &Envelope<C>,
+        rng: &mut RNG,
+    ) -> Result<Envelope<C>, Error<C>> {
+        self.signers
+            .get_mut(&envelope.run_id())
+            .ok_or(Error::RunIdMismatch)?
+            .receive_envelope(envelope, rng)
+    }
+}
+
+/// Represents several coordinators that sign the same message in parallel
+/// and takes the first valid [`Signature`].
+#[derive(Debug)]
+pub struct MultiCoordinator<C: Ciphersuite, M: Metrics = ()> {
+    verifying_key: VerifyingKey<C>,
+    message: Vec<u8>,
+    coordinators: BTreeMap<RunId, Coordinator<C, M>>,
+    signature: Option<Signature<C>>,
+}
+
+impl<C: Ciphersuite> MultiCoordinator<C> {
+    /// Creates a new [`MultiCoordinator`] with a [`Coordinator`] for each of
+    /// `run_ids`.
+    ///
+    /// Returns [`Error::DuplicateRunId`] if `run_ids` are not distinct.
+    pub fn new(
+        max_signers: u16,
+        min_signers: u16,
+        public_key_package: PublicKeyPackage<C>,
+        message: Vec<u8>,
+        run_ids: impl IntoIterator<Item = RunId>,
+    ) -> Result<Self, Error<C>> {
+        let metrics = run_ids.into_iter().map(|run_id| (run_id, ()));
+        Self::with_metrics(
+            max_signers,
+            min_signers,
+            public_key_package,
+            message,
+            metrics,
+        )
+    }
+}
+
+impl<C: Ciphersuite, M: Metrics> MultiCoordinator<C, M> {
+    /// Creates a new [`MultiCoordinator`] with a [`Coordinator`] for each of
+    /// the given run ids that reports metrics of its run to its own `metrics`
+    /// (see [`Coordinator::with_metrics`]).
+    ///
+    /// Returns [`Error::DuplicateRunId`] if run ids are not distinct.
+    pub fn with_metrics(
+        max_signers: u16,
+        min_signers: u16,
+        public_key_package: PublicKeyPackage<C>,
+        message: Vec<u8>,
+        metrics: impl IntoIterator<Item = (RunId, M)>,
+    ) -> Result<Self, Error<C>> {
+        let mut coordinators = BTreeMap::new();
+        for (run_id, metrics) in metrics {
+            let coordinator = Coordinator::with_metrics(
+                max_signers,
+                min_signers,
+                public_key_package.clone(),
+                message.clone(),
+                metrics,
+            )?
+            .with_run_id(run_id);
+            if coordinators.insert(run_id, coordinator).is_some() {
+                return Err(Error::DuplicateRunId);
+            }
+        }
+
+        Ok(Self {
+            verifying_key: *public_key_package.verifying_key(),
+            message,
+            coordinators,
+            signature: None,
+        })
+    }
+
+    /// Returns [`Coordinator`] of the run `run_id`.
+    pub fn coordinator(&self, run_id: RunId) -> Option<&Coordinator<C, M>> {
+        self.coordinators.get(&run_id)
+    }
+
+    /// Registers long-term identity keys of signers in every [`Coordinator`]
+    /// (see [`Coordinator::with_identity_keys`]).
+    pub fn with_identity_keys(
+        mut self,
+        identity_keys: BTreeMap<Identifier<C>, VerifyingKey<C>>,
+    ) -> Self {
+        self.coordinators = self
+            .coordinators
+            .into_iter()
+            .map(|(run_id, coordinator)| {
+                (
+                    run_id,
+                    coordinator.with_identity_keys(identity_keys.clone()),
+                )
+            })
+            .collect();
+        self
+    }
+
+    /// Returns the first valid [`Signature`], if any.
+    pub fn signature(&self) -> Option<Signature<C>> {
+        self.signature
+    }
+
+    /// Same as [`Coordinator::receive_envelope`] with [`Coordinator`] of the
+    /// run the message belongs to.
+    ///
+    /// Once any of the coordinators produces [`Signature`] that is valid for
+    /// the group verifying key, it is kept and returned in
+    /// [`SessionStatus::Finished`] for every subsequent message. Errors of one
+    /// coordinator do not affect the others.
+    ///
+    /// Returns [`Error::RunIdMismatch`] if the message belongs to none of the
+    /// runs.
+    pub fn receive_envelope(
+        &mut self,
+        envelope: Envelope<C>,
+    ) -> Result<SessionStatus<C>, Error<C>> {
+        self.receive_with(envelope.run_id(), |coordinator| {
+            coordinator.receive_envelope(envelope)
+        })
+    }
+
+    /// Same as [`MultiCoordinator::receive_envelope`], but receives reply of
+    /// the signer signed with its long-term identity key (see
+    /// [`Coordinator::receive_signed_envelope`]).
+    pub fn receive_signed_envelope(
+        &mut self,
+        signed_envelope: SignedEnvelope<C>,
+    ) -> Result<SessionStatus<C>, Error<C>> {
+        self.receive_with(signed_envelope.envelope().run_id(), |coordinator| {
+            coordinator.receive_signed_envelope(signed_envelope)
+        })
+    }
+
+    fn receive_with(
+        &mut self,
+        run_id: RunId,
+        receive: impl FnOnce(&mut Coordinator<C, M>) -> Result<SessionStatus<C>, Error<C>>,
+    ) -> Result<SessionStatus<C>, Error<C>> {
+        if let Some(signature) = self.signature {
+            return Ok(SessionStatus::Finished { signature });
+        }
+
+        let session_status = receive(
+            self.coordinators
+                .get_mut(&run_id)
+                .ok_or(Error::RunIdMismatch)?,
+        )?;
+
+        if let SessionStatus::Finished { signature } = &session_status {
+            self.verifying_key.verify(&self.message, signature)?;
+            self.signature = Some(*signature);
+        }
+
+        Ok(session_status)
+    }
+
+    /// Returns [`SigningPackage`](frost_core::SigningPackage) of the session
+    /// of the run `run_id` wrapped in [`Envelope`] (see
+    /// [`Coordinator::envelope`]).
+    pub fn envelope(&self, run_id: RunId, session_id: SessionId) -> Option<Envelope<C>> {
+        self.coordinators.get(&run_id)?.envelope(session_id)
+    }
+}
@@ -0,0 +1,277 @@
+use crate::Error;
+use alloc::vec::Vec;
+use core::fmt;
+use frost_core::{
+    round1::{SigningCommitments, SigningNonces},
+    Ciphersuite,
+};
+use zeroize::Zeroize;
+
+/// Represents storage of unused [`SigningNonces`].
+///
+/// Implementations must provide a write-ahead guarantee: [`NonceStore::insert`]
+/// must not return before [`SigningNonces`] are persisted, and
+/// [`NonceStore::take`] must not return before their removal is persisted.
+/// [`Signer`](crate::Signer) publishes [`SigningCommitments`] only after they
+/// are inserted and signs only after [`SigningNonces`] are taken, so the same
+/// [`SigningNonces`] are never used twice, even across restarts.
+pub trait NonceStore<C: Ciphersuite> {
+    /// Stores [`SigningNonces`].
+    fn insert(&mut self, signing_nonces: SigningNonces<C>) -> Result<(), Error<C>>;
+
+    /// Returns `true` if the store contains [`SigningNonces`] with the given
+    /// [`SigningCommitments`].
+    fn contains(&self, signing_commitments: &SigningCommitments<C>) -> bool;
+
+    /// Removes and returns [`SigningNonces`] with the given
+    /// [`SigningCommitments`].
+    fn take(
+        &mut self,
+        signing_commitments: &SigningCommitments<C>,
+    ) -> Result<Option<SigningNonces<C>>, Error<C>>;
+}
+
+/// Represents in-memory [`NonceStore`].
+///
+/// Unused [`SigningNonces`] are lost on restart, so they can never be reused.
+/// [`SigningNonces`] are zeroized on drop and are redacted in
+/// [`Debug`](fmt::Debug) output.
+pub struct MemoryNonceStore<C: Ciphersuite> {
+    pub(crate) signing_nonces: Vec<SigningNonces<C>>,
+}
+
+impl<C: Ciphersuite> MemoryNonceStore<C> {
+    /// Creates a new empty [`MemoryNonceStore`].
+    pub fn new() -> Self {
+        Self {
+            signing_nonces: Vec::new(),
+        }
+    }
+}
+
+impl<C: Ciphersuite> Default for MemoryNonceStore<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Ciphersuite> NonceStore<C> for MemoryNonceStore<C> {
+    fn insert(&mut self, signing_nonces: SigningNonces<C>) -> Result<(), Error<C>> {
+        self.signing_nonces.push(signing_nonces);
+        Ok(())
+    }
+
+    fn contains(&self, signing_commitments: &SigningCommitments<C>) -> bool {
+        position(&self.signing_nonces, signing_commitments).is_some()
+    }
+
+    fn take(
+        &mut self,
+        signing_commitments: &SigningCommitments<C>,
+    ) -> Result<Option<SigningNonces<C>>, Error<C>> {
+        Ok(position(&self.signing_nonces, signing_commitments)
+            .map(|index| self.signing_nonces.swap_remove(index)))
+    }
+}
+
+impl<C: Ciphersuite> fmt::Debug for MemoryNonceStore<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryNonceStore")
+            .field("signing_nonces", &self.signing_nonces.len())
+            .finish()
+    }
+}
+
+impl<C: Ciphersuite> Drop for MemoryNonceStore<C> {
+    fn drop(&mut self) {
+        self.signing_nonces.iter_mut().for_each(Zeroize::zeroize);
+    }
+}
+
+fn position<C: Ciphersuite>(
+    signing_nonces: &[SigningNonces<C>],
+    signing_commitments: &SigningCommitments<C>,
+) -> Option<usize> {
+    signing_nonces
+        .iter()
+        .position(|signing_nonces| signing_nonces.commitments() == signing_commitments)
+}
+
+#[cfg(all(feature = "std", feature = "serialization"))]
+mod file {
+    use super::{position, NonceStore};
+    use crate::{Error, NonceStoreError};
+    use frost_core::{
+        round1::{SigningCommitments, SigningNonces},
+        Ciphersuite,
+    };
+    use std::{
+        fmt, fs,
+        io::Write,
+        path::{Path, PathBuf},
+    };
+    use zeroize::Zeroize;
+
+    /// Represents file-backed [`NonceStore`].
+    ///
+    /// Every change rewrites a temporary file, syncs it to disk and atomically
+    /// renames it over the store file. [`SigningNonces`] are stored
+    /// unencrypted, so the file must be protected like the key share.
+    pub struct FileNonceStore<C: Ciphersuite> {
+        path: PathBuf,
+        signing_nonces: Vec<SigningNonces<C>>,
+    }
+
+    impl<C: Ciphersuite> FileNonceStore<C> {
+        /// Opens [`FileNonceStore`] at `path`, loading previously stored
+        /// [`SigningNonces`] if the file exists.
+        pub fn open(path: impl AsRef<Path>) -> Result<Self, Error<C>> {
+            let path = path.as_ref().to_path_buf();
+
+            let signing_nonces = match fs::read(&path) {
+                Ok(mut bytes) => {
+                    let signing_nonces = Self::deserialize(&bytes);
+                    bytes.zeroize();
+                    signing_nonces?
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+                Err(err) => return Err(NonceStoreError::from(err).into()),
+            };
+
+            Ok(Self {
+                path,
+                signing_nonces,
+            })
+        }
+
+        /// Deserializes [`SigningNonces`] from contents of the store file.
+        ///
+        /// Already deserialized [`SigningNonces`] are zeroized if the rest of
+        /// the file is malformed.
+        fn deserialize(mut bytes: &[u8]) -> Result<Vec<SigningNonces<C>>, Error<C>> {
+            let mut signing_nonces = Vec::new();
+
+            let result = (|| -> Result<(), Error<C>> {
+                while !bytes.is_empty() {
+                    if bytes.len() < 4 {
+                        return Err(NonceStoreError::Malformed.into());
+                    }
+                    let (len, rest) = bytes.split_at(4);
+                    let len = u32::from_be_bytes([len[0], len[1], len[2], len[3]]) as usize;
+                    if rest.len() < len {
+                        return Err(NonceStoreError::Malformed.into());
+                    }
+                    let (serialization, rest) = rest.split_at(len);
+                    signing_nonces.push(SigningNonces::deserialize(serialization)?);
+                    bytes = rest;
+                }
+                Ok(())
+            })();
+
+            if let Err(err) = result {
+                signing_nonces.iter_mut().for_each(Zeroize::zeroize);
+                return Err(err);
+            }
+
+            Ok(signing_nonces)
+        }
+
+        /// Persists all [`SigningNonces`] to the store file.
+        fn persist(&self) -> Result<(), Error<C>> {
+            let mut bytes = Vec::new();
+            for signing_nonces in &self.signing_nonces {
+                let mut serialization = signing_nonces.serialize()?;
+                let len = u32::try_from(serialization.len()).map_err(|_| NonceStoreError::Other)?;
+                bytes.extend_from_slice(&len.to_be_bytes());
+                bytes.extend_from_slice(&serialization);
+                serialization.zeroize();
+            }
+
+            let result = (|| -> std::io::Result<()> {
+                let mut tmp_path = self.path.clone().into_os_string();
+                tmp_path.push(".tmp");
+                let tmp_path = PathBuf::from(tmp_path);
+
+                // Signing nonces are as secret as the key share, so the file is
+                // readable only by its owner. Permissions apply only on creation,
+                // so a stale temporary file is removed first.
+                match fs::remove_file(&tmp_path) {
+                    Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err),
+                    _ => {}
+                }
+                let mut options = fs::OpenOptions::new();
+                options.write(true).create(true).truncate(true);
+                #[cfg(unix)]
+                std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+                let mut file = options.open(&tmp_path)?;
+                file.write_all(&bytes)?;
+                file.sync_all()?;
+                fs::rename(&tmp_path, &self.path)?;
+
+                if let Some(parent) = self.path.parent() {
+                    if let Ok(dir) = fs::File::open(parent) {
+                        dir.sync_all()?;
+                    }
+                }
+
+                Ok(())
+            })();
+            bytes.zeroize();
+
+            result.map_err(|err| NonceStoreError::from(err).into())
+        }
+    }
+
+    impl<C: Ciphersuite> NonceStore<C> for FileNonceStore<C> {
+        fn insert(&mut self, signing_nonces: SigningNonces<C>) -> Result<(), Error<C>> {
+            self.signing_nonces.push(signing_nonces);
+            if let Err(err) = self.persist() {
+                if let Some(mut signing_nonces) = self.signing_nonces.pop() {
+                    signing_nonces.zeroize();
+                }
+                return Err(err);
+            }
+            Ok(())
+        }
+
+        fn contains(&self, signing_commitments: &SigningCommitments<C>) -> bool {
+            position(&self.signing_nonces, signing_commitments).is_some()
+        }
+
+        fn take(
+            &mut self,
+            signing_commitments: &SigningCommitments<C>,
+        ) -> Result<Option<SigningNonces<C>>, Error<C>> {
+            let Some(index) = position(&self.signing_nonces, signing_commitments) else {
+                return Ok(None);
+            };
+
+            let signing_nonces = self.signing_nonces.swap_remove(index);
+            if let Err(err) = self.persist() {
+                self.signing_nonces.push(signing_nonces);
+                return Err(err);
+            }
+
+            Ok(Some(signing_nonces))
+        }
+    }
+
+    impl<C: Ciphersuite> fmt::Debug for FileNonceStore<C> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("FileNonceStore")
+                .field("path", &self.path)
+                .field("signing_nonces", &self.signing_nonces.len())
+                .finish()
+        }
+    }
+
+    impl<C: Ciphersuite> Drop for FileNonceStore<C> {
+        fn drop(&mut self) {
+            self.signing_nonces.iter_mut().for_each(Zeroize::zeroize);
+        }
+    }
+}
+
+#[cfg(all(feature = "std", feature = "serialization"))]
+pub use file::*;
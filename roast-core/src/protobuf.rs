@@ -0,0 +1,248 @@
+//! Protobuf messages of `roast.v1` package and their conversions to and from
+//! [`Envelope`](crate::Envelope).
+
+use crate::{Error, RunId, PROTOCOL_VERSION};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use frost_core::{
+    round1::{self, NonceCommitment},
+    round2::SignatureShare,
+    Ciphersuite, Identifier,
+};
+use prost::Message;
+
+/// Represents protobuf schema of messages in this module.
+pub const SCHEMA: &str = include_str!("../proto/roast.proto");
+
+/// Represents signing commitments of signer for one session.
+#[derive(Clone, PartialEq, Message)]
+pub struct SigningCommitments {
+    /// Serialized commitment to hiding nonce.
+    #[prost(bytes = "vec", tag = "1")]
+    pub hiding: Vec<u8>,
+    /// Serialized commitment to binding nonce.
+    #[prost(bytes = "vec", tag = "2")]
+    pub binding: Vec<u8>,
+}
+
+/// Represents reply of signer to coordinator.
+#[derive(Clone, PartialEq, Message)]
+pub struct Reply {
+    /// Serialized identifier of the signer.
+    #[prost(bytes = "vec", tag = "1")]
+    pub identifier: Vec<u8>,
+    /// Serialized signature share for signing package of the session, if any.
+    #[prost(bytes = "vec", optional, tag = "2")]
+    pub signature_share: Option<Vec<u8>>,
+    /// Signing commitments for the next session.
+    #[prost(message, optional, tag = "3")]
+    pub signing_commitments: Option<SigningCommitments>,
+}
+
+/// Represents signing commitments of one signer of the session.
+#[derive(Clone, PartialEq, Message)]
+pub struct SignerCommitments {
+    /// Serialized identifier of the signer.
+    #[prost(bytes = "vec", tag = "1")]
+    pub identifier: Vec<u8>,
+    /// Signing commitments of the signer.
+    #[prost(message, optional, tag = "2")]
+    pub signing_commitments: Option<SigningCommitments>,
+}
+
+/// Represents signing package sent by coordinator to signers of the session.
+#[derive(Clone, PartialEq, Message)]
+pub struct SigningPackage {
+    /// Signing commitments of all signers of the session, each signer once.
+    #[prost(message, repeated, tag = "1")]
+    pub commitments: Vec<SignerCommitments>,
+    /// Message to sign.
+    #[prost(bytes = "vec", tag = "2")]
+    pub message: Vec<u8>,
+}
+
+/// Represents versioned message.
+#[derive(Clone, PartialEq, Message)]
+pub struct Envelope {
+    /// Version of wire protocol.
+    #[prost(uint32, tag = "1")]
+    pub version: u32,
+    /// Ciphersuite ID.
+    #[prost(string, tag = "2")]
+    pub ciphersuite: String,
+    /// Identifier of coordinator run.
+    #[prost(bytes = "vec", tag = "3")]
+    pub run_id: Vec<u8>,
+    /// Identifier of the session.
+    #[prost(uint32, tag = "4")]
+    pub session_id: u32,
+    /// Payload of the message.
+    #[prost(oneof = "envelope::Payload", tags = "5, 6")]
+    pub payload: Option<envelope::Payload>,
+}
+
+/// Nested types of [`Envelope`].
+pub mod envelope {
+    /// Represents all possible payloads of [`Envelope`].
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub enum Payload {
+        /// Reply of signer to coordinator.
+        #[prost(message, tag = "5")]
+        Reply(super::Reply),
+        /// Signing package sent by coordinator to signers of the session.
+        #[prost(message, tag = "6")]
+        SigningPackage(super::SigningPackage),
+    }
+}
+
+impl<C: Ciphersuite> TryFrom<&round1::SigningCommitments<C>> for SigningCommitments {
+    type Error = Error<C>;
+
+    fn try_from(signing_commitments: &round1::SigningCommitments<C>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            hiding: signing_commitments.hiding().serialize()?,
+            binding: signing_commitments.binding().serialize()?,
+        })
+    }
+}
+
+impl<C: Ciphersuite> TryFrom<SigningCommitments> for round1::SigningCommitments<C> {
+    type Error = Error<C>;
+
+    fn try_from(signing_commitments: SigningCommitments) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            NonceCommitment::deserialize(&signing_commitments.hiding)?,
+            NonceCommitment::deserialize(&signing_commitments.binding)?,
+        ))
+    }
+}
+
+impl<C: Ciphersuite> TryFrom<&frost_core::SigningPackage<C>> for SigningPackage {
+    type Error = Error<C>;
+
+    fn try_from(signing_package: &frost_core::SigningPackage<C>) -> Result<Self, Self::Error> {
+        let commitments = signing_package
+            .signing_commitments()
+            .iter()
+            .map(|(identifier, signing_commitments)| {
+                Ok(SignerCommitments {
+                    identifier: identifier.serialize().as_ref().to_vec(),
+                    signing_commitments: Some(signing_commitments.try_into()?),
+                })
+            })
+            .collect::<Result<_, Error<C>>>()?;
+
+        Ok(Self {
+            commitments,
+            message: signing_package.message().to_vec(),
+        })
+    }
+}
+
+impl<C: Ciphersuite> TryFrom<SigningPackage> for frost_core::SigningPackage<C> {
+    type Error = Error<C>;
+
+    /// Returns [`Error::MalformedMessage`] if signing commitments are missing
+    /// or some signer occurs more than once.
+    fn try_from(signing_package: SigningPackage) -> Result<Self, Self::Error> {
+        let len = signing_package.commitments.len();
+        let signing_commitments: BTreeMap<_, _> = signing_package
+            .commitments
+            .into_iter()
+            .map(|signer_commitments| {
+                Ok((
+                    Identifier::deserialize(&signer_commitments.identifier)?,
+                    signer_commitments
+                        .signing_commitments
+                        .ok_or(Error::MalformedMessage)?
+                        .try_into()?,
+                ))
+            })
+            .collect::<Result<_, Error<C>>>()?;
+        if signing_commitments.len() != len {
+            return Err(Error::MalformedMessage);
+        }
+
+        Ok(Self::new(signing_commitments, &signing_package.message))
+    }
+}
+
+impl<C: Ciphersuite> TryFrom<&crate::Envelope<C>> for Envelope {
+    type Error = Error<C>;
+
+    fn try_from(envelope: &crate::Envelope<C>) -> Result<Self, Self::Error> {
+        let payload = match envelope.payload() {
+            crate::Payload::Reply {
+                identifier,
+                signature_share,
+                signing_commitments,
+            } => envelope::Payload::Reply(Reply {
+                identifier: identifier.serialize().as_ref().to_vec(),
+                signature_share: signature_share
+                    .map(|signature_share| signature_share.serialize().as_ref().to_vec()),
+                signing_commitments: Some(signing_commitments.try_into()?),
+            }),
+            crate::Payload::SigningPackage(signing_package) => {
+                envelope::Payload::SigningPackage(signing_package.try_into()?)
+            }
+        };
+
+        Ok(Self {
+            version: PROTOCOL_VERSION.into(),
+            ciphersuite: C::ID.into(),
+            run_id: envelope.run_id().as_ref().to_vec(),
+            session_id: envelope.session_id().into(),
+            payload: Some(payload),
+        })
+    }
+}
+
+impl<C: Ciphersuite> TryFrom<Envelope> for crate::Envelope<C> {
+    type Error = Error<C>;
+
+    fn try_from(envelope: Envelope) -> Result<Self, Self::Error> {
+        let version = u16::try_from(envelope.version).map_err(|_| Error::MalformedMessage)?;
+        if version != PROTOCOL_VERSION {
+            return Err(Error::UnsupportedProtocolVersion(version));
+        }
+        if envelope.ciphersuite != C::ID {
+            return Err(Error::CiphersuiteMismatch);
+        }
+
+        let run_id = <[u8; 32]>::try_from(envelope.run_id.as_slice())
+            .map_err(|_| Error::MalformedMessage)?;
+        let session_id = u16::try_from(envelope.session_id).map_err(|_| Error::MalformedMessage)?;
+
+        let payload = match envelope.payload.ok_or(Error::MalformedMessage)? {
+            envelope::Payload::Reply(reply) => crate::Payload::Reply {
+                identifier: Identifier::deserialize(&reply.identifier)?,
+                signature_share: reply
+                    .signature_share
+                    .map(|signature_share| SignatureShare::deserialize(&signature_share))
+                    .transpose()?,
+                signing_commitments: reply
+                    .signing_commitments
+                    .ok_or(Error::MalformedMessage)?
+                    .try_into()?,
+            },
+            envelope::Payload::SigningPackage(signing_package) => {
+                crate::Payload::SigningPackage(signing_package.try_into()?)
+            }
+        };
+
+        Ok(Self::new(RunId::new(run_id), session_id, payload))
+    }
+}
+
+impl<C: Ciphersuite> crate::Envelope<C> {
+    /// Encodes [`Envelope`](crate::Envelope) as protobuf [`Envelope`].
+    pub fn encode_protobuf(&self) -> Result<Vec<u8>, Error<C>> {
+        Ok(Envelope::try_from(self)?.encode_to_vec())
+    }
+
+    /// Decodes [`Envelope`](crate::Envelope) from protobuf [`Envelope`].
+    pub fn decode_protobuf(bytes: &[u8]) -> Result<Self, Error<C>> {
+        Envelope::decode(bytes)
+            .map_err(|_| Error::MalformedMessage)?
+            .try_into()
+    }
+}
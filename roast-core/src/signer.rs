@@ -1,58 +1,325 @@
-use crate::Error;
+use crate::{
+    verify_signing_package_digests, Envelope, Error, MaliciousCoordinatorError, MemoryNonceStore,
+    NonceStore, Payload, RunId, SigningPackageDigest,
+};
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::fmt;
 use frost_core::{
     keys::KeyPackage,
     round1::{self, SigningCommitments, SigningNonces},
     round2::{self, SignatureShare},
-    Ciphersuite, SigningPackage,
+    Ciphersuite, Identifier, SigningPackage,
 };
 use rand_core::{CryptoRng, RngCore};
+use zeroize::Zeroize;
 
 /// Represents signer.
-#[derive(Debug)]
-pub struct Signer<C: Ciphersuite> {
+///
+/// All unused [`SigningNonces`] are kept in [`NonceStore`]. [`KeyPackage`] is
+/// zeroized on drop and is redacted in [`Debug`](fmt::Debug) output.
+pub struct Signer<C: Ciphersuite, S: NonceStore<C> = MemoryNonceStore<C>> {
     key_package: KeyPackage<C>,
-    signing_nonces: SigningNonces<C>,
+    message: Vec<u8>,
+    run_id: RunId,
+    signing_commitments: SigningCommitments<C>,
+    nonce_store: S,
 }
 
 impl<C: Ciphersuite> Signer<C> {
-    /// Creates a new [`Signer`] and generates [`SigningNonces`] for the first
-    /// round of FROST.
-    pub fn new<RNG: RngCore + CryptoRng>(key_package: KeyPackage<C>, rng: &mut RNG) -> Self {
-        let (signing_nonces, _) = round1::commit(key_package.signing_share(), rng);
-        Self {
+    /// Creates a new [`Signer`] that expects to sign `message` and generates
+    /// [`SigningNonces`] for the first round of FROST.
+    pub fn new<RNG: RngCore + CryptoRng>(
+        key_package: KeyPackage<C>,
+        message: Vec<u8>,
+        rng: &mut RNG,
+    ) -> Result<Self, Error<C>> {
+        let run_id = RunId::derive(key_package.verifying_key(), &message)?;
+        let (signing_nonces, signing_commitments) =
+            round1::commit(key_package.signing_share(), rng);
+        let mut nonce_store = MemoryNonceStore::new();
+        nonce_store.signing_nonces.push(signing_nonces);
+
+        Ok(Self {
             key_package,
-            signing_nonces,
-        }
+            message,
+            run_id,
+            signing_commitments,
+            nonce_store,
+        })
+    }
+}
+
+impl<C: Ciphersuite, S: NonceStore<C>> Signer<C, S> {
+    /// Creates a new [`Signer`] that expects to sign `message`, keeps unused
+    /// [`SigningNonces`] in `nonce_store` and generates [`SigningNonces`] for
+    /// the first round of FROST.
+    ///
+    /// [`SigningNonces`] already kept in `nonce_store` (e.g. before restart)
+    /// remain usable.
+    pub fn with_nonce_store<RNG: RngCore + CryptoRng>(
+        key_package: KeyPackage<C>,
+        message: Vec<u8>,
+        mut nonce_store: S,
+        rng: &mut RNG,
+    ) -> Result<Self, Error<C>> {
+        let run_id = RunId::derive(key_package.verifying_key(), &message)?;
+        let (signing_nonces, signing_commitments) =
+            round1::commit(key_package.signing_share(), rng);
+        nonce_store.insert(signing_nonces)?;
+
+        Ok(Self {
+            key_package,
+            message,
+            run_id,
+            signing_commitments,
+            nonce_store,
+        })
+    }
+
+    /// Sets identifier of coordinator run this signer takes part in.
+    ///
+    /// By default it is derived from verifying key and message (see
+    /// [`RunId::derive`]).
+    pub fn with_run_id(mut self, run_id: RunId) -> Self {
+        self.run_id = run_id;
+        self
+    }
+
+    /// Returns identifier of coordinator run this signer takes part in.
+    pub fn run_id(&self) -> RunId {
+        self.run_id
+    }
+
+    /// Generates `count` additional [`SigningNonces`] for the first round of
+    /// FROST and returns their [`SigningCommitments`].
+    ///
+    /// The caller can publish returned commitments to the coordinator in a
+    /// batch (see [`Coordinator::preprocess`](crate::Coordinator::preprocess)).
+    /// Each of them is used at most once.
+    pub fn preprocess<RNG: RngCore + CryptoRng>(
+        &mut self,
+        count: usize,
+        rng: &mut RNG,
+    ) -> Result<Vec<SigningCommitments<C>>, Error<C>> {
+        (0..count)
+            .map(|_| {
+                let (signing_nonces, signing_commitments) =
+                    round1::commit(self.key_package.signing_share(), rng);
+                self.nonce_store.insert(signing_nonces)?;
+                Ok(signing_commitments)
+            })
+            .collect()
     }
 
-    /// Regenerates [`SigningNonces`] for the first round of FROST. The caller
+    /// Generates new [`SigningNonces`] for the first round of FROST. The caller
     /// should take care to send the coordinator a new [`SigningCommitments`].
-    pub(crate) fn regenerate_signing_nonces<RNG: RngCore + CryptoRng>(&mut self, rng: &mut RNG) {
-        let (signing_nonces, _) = round1::commit(self.key_package.signing_share(), rng);
-        self.signing_nonces = signing_nonces;
+    fn generate_signing_nonces<RNG: RngCore + CryptoRng>(
+        &mut self,
+        rng: &mut RNG,
+    ) -> Result<(), Error<C>> {
+        let (signing_nonces, signing_commitments) =
+            round1::commit(self.key_package.signing_share(), rng);
+        self.nonce_store.insert(signing_nonces)?;
+        self.signing_commitments = signing_commitments;
+
+        Ok(())
+    }
+
+    /// Regenerates [`SigningNonces`] for the first round of FROST, discarding
+    /// current ones. The caller should take care to send the coordinator a new
+    /// [`SigningCommitments`].
+    pub(crate) fn regenerate_signing_nonces<RNG: RngCore + CryptoRng>(
+        &mut self,
+        rng: &mut RNG,
+    ) -> Result<(), Error<C>> {
+        if let Some(mut signing_nonces) = self.nonce_store.take(&self.signing_commitments)? {
+            signing_nonces.zeroize();
+        }
+        self.generate_signing_nonces(rng)
     }
 
     /// Returns the [`SigningCommitments`], i.e. the public part of
     /// [`SigningNonces`] that is used for the first round of FROST.
     pub fn signing_commitments(&self) -> SigningCommitments<C> {
-        *self.signing_nonces.commitments()
+        self.signing_commitments
+    }
+
+    /// Returns the [`NonceStore`] with unused [`SigningNonces`].
+    pub fn nonce_store(&self) -> &S {
+        &self.nonce_store
     }
 
     /// Receives a [`SigningPackage`] from the coordinator to create a
     /// [`SignatureShare`] that is used in the second round of FROST.
     ///
-    /// Also regenerates [`SigningNonces`] for the first round of FROST. The
-    /// caller should take care to send the coordinator a new
-    /// [`SigningCommitments`].
+    /// The [`SigningPackage`] may refer either to current
+    /// [`SigningCommitments`] or to any unused preprocessed ones (see
+    /// [`Signer::preprocess`]). Used [`SigningNonces`] are removed from
+    /// [`NonceStore`] before signing, so they are never used twice.
+    ///
+    /// If current [`SigningCommitments`] are used, also regenerates
+    /// [`SigningNonces`] for the first round of FROST. Otherwise current
+    /// [`SigningCommitments`] are kept, since they have already been sent to
+    /// the coordinator, which uses them in a later session. Either way the
+    /// caller should take care to send the coordinator
+    /// [`Signer::signing_commitments`].
+    ///
+    /// Returns [`Error::MaliciousCoordinator`] if the [`SigningPackage`] does
+    /// not pass [`Signer::verify_signing_package`]. In this case
+    /// [`SigningNonces`] are not used and therefore not regenerated.
     pub fn receive<RNG: RngCore + CryptoRng>(
         &mut self,
         signing_package: &SigningPackage<C>,
         rng: &mut RNG,
     ) -> Result<SignatureShare<C>, Error<C>> {
-        let signature_share =
-            round2::sign(signing_package, &self.signing_nonces, &self.key_package)?;
-        self.regenerate_signing_nonces(rng);
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "Signer::receive",
+            identifier = ?self.key_package.identifier()
+        )
+        .entered();
+
+        let signing_commitments = match self.verify_signing_package(signing_package) {
+            Ok(signing_commitments) => signing_commitments,
+            Err(err) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %err, "invalid signing package");
+
+                return Err(err.into());
+            }
+        };
+
+        let mut signing_nonces: SigningNonces<C> = self
+            .nonce_store
+            .take(&signing_commitments)?
+            .ok_or(MaliciousCoordinatorError::InvalidSigningCommitments)?;
+        let signature_share = round2::sign(signing_package, &signing_nonces, &self.key_package);
+        signing_nonces.zeroize();
+
+        if signing_commitments == self.signing_commitments {
+            self.generate_signing_nonces(rng)?;
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(success = signature_share.is_ok(), "signing package signed");
+
+        signature_share.map_err(Error::Frost)
+    }
+
+    /// Returns the first reply of this signer to the coordinator wrapped in
+    /// [`Envelope`], i.e. current [`SigningCommitments`] without
+    /// [`SignatureShare`].
+    pub fn envelope(&self) -> Envelope<C> {
+        Envelope::new(
+            self.run_id,
+            0,
+            Payload::Reply {
+                identifier: *self.key_package.identifier(),
+                signature_share: None,
+                signing_commitments: self.signing_commitments,
+            },
+        )
+    }
+
+    /// Same as [`Signer::receive`], but receives [`SigningPackage`] wrapped in
+    /// [`Envelope`] and returns reply to the coordinator wrapped in
+    /// [`Envelope`] of the same session.
+    ///
+    /// Returns [`Error::RunIdMismatch`] if the message belongs to another
+    /// coordinator run. In this case [`SigningNonces`] are not used.
+    pub fn receive_envelope<RNG: RngCore + CryptoRng>(
+        &mut self,
+        envelope: &Envelope<C>,
+        rng: &mut RNG,
+    ) -> Result<Envelope<C>, Error<C>> {
+        let Payload::SigningPackage(signing_package) = envelope.payload() else {
+            return Err(Error::MalformedMessage);
+        };
+        if envelope.run_id() != self.run_id {
+            return Err(Error::RunIdMismatch);
+        }
+
+        let signature_share = self.receive(signing_package, rng)?;
+
+        Ok(Envelope::new(
+            self.run_id,
+            envelope.session_id(),
+            Payload::Reply {
+                identifier: *self.key_package.identifier(),
+                signature_share: Some(signature_share),
+                signing_commitments: self.signing_commitments,
+            },
+        ))
+    }
+
+    /// Same as [`Signer::receive`], but also verifies that digests of
+    /// [`SigningPackage`] received by other signers of the same session
+    /// match the digest of `signing_package`.
+    ///
+    /// Returns [`MaliciousCoordinatorError::Equivocation`] if the coordinator
+    /// sent different signing packages to signers of the same session and
+    /// [`MaliciousCoordinatorError::MissingSigningPackageDigest`] if digest of
+    /// any other signer of the session is missing.
+    pub fn receive_with_digests<RNG: RngCore + CryptoRng>(
+        &mut self,
+        signing_package: &SigningPackage<C>,
+        signing_package_digests: &BTreeMap<Identifier<C>, SigningPackageDigest>,
+        rng: &mut RNG,
+    ) -> Result<SignatureShare<C>, Error<C>> {
+        verify_signing_package_digests(
+            self.key_package.identifier(),
+            signing_package,
+            signing_package_digests,
+        )?;
+        self.receive(signing_package, rng)
+    }
+
+    /// Verifies that the [`SigningPackage`] received from the coordinator:
+    /// - contains [`SigningCommitments`] of this signer that have unused
+    ///   [`SigningNonces`] in [`NonceStore`];
+    /// - contains exactly threshold number of [`SigningCommitments`];
+    /// - contains the message this signer expects to sign.
+    ///
+    /// Returns [`SigningCommitments`] of this signer if successful.
+    pub fn verify_signing_package(
+        &self,
+        signing_package: &SigningPackage<C>,
+    ) -> Result<SigningCommitments<C>, MaliciousCoordinatorError> {
+        let signing_commitments = signing_package
+            .signing_commitments()
+            .get(self.key_package.identifier())
+            .copied()
+            .ok_or(MaliciousCoordinatorError::MissingSigningCommitments)?;
+
+        if !self.nonce_store.contains(&signing_commitments) {
+            return Err(MaliciousCoordinatorError::InvalidSigningCommitments);
+        }
+
+        if signing_package.signing_commitments().len() != *self.key_package.min_signers() as usize {
+            return Err(MaliciousCoordinatorError::InvalidNumberOfSigningCommitments);
+        }
+
+        if signing_package.message() != &self.message[..] {
+            return Err(MaliciousCoordinatorError::InvalidMessage);
+        }
+
+        Ok(signing_commitments)
+    }
+}
+
+impl<C: Ciphersuite, S: NonceStore<C>> fmt::Debug for Signer<C, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Signer")
+            .field("identifier", self.key_package.identifier())
+            .field("key_package", &"<redacted>")
+            .field("message", &self.message)
+            .field("signing_commitments", &self.signing_commitments)
+            .finish_non_exhaustive()
+    }
+}
 
-        Ok(signature_share)
+impl<C: Ciphersuite, S: NonceStore<C>> Drop for Signer<C, S> {
+    fn drop(&mut self) {
+        self.key_package.zeroize();
     }
 }
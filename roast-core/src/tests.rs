@@ -5,12 +5,258 @@ use crate::{
         keys::{self, IdentifierList, KeyPackage},
         Ciphersuite,
     },
-    Coordinator, Error, SessionStatus, Signer,
+    Coordinator, CoordinatorEvent, Envelope, Error, LivenessCoordinator, MaliciousCoordinatorError,
+    MaliciousSignerError, MemoryNonceStore, Metrics, MultiCoordinator, MultiSigner, NonceStore,
+    Payload, RunId, SessionStatus, SignedEnvelope, Signer, SignerStatus, SigningPackageDigest,
+    TimeoutPolicy, Transcript, TranscriptInput, TranscriptOutput, TranscriptRecorder,
+};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use core::time::Duration;
+use frost_core::{
+    keys::VerifyingShare, round2::SignatureShare, Field, Group, Identifier, SigningKey,
+    SigningPackage, VerifyingKey,
 };
-use alloc::collections::BTreeMap;
-use frost_core::{round2::SignatureShare, Field, Group};
 use rand::{seq::SliceRandom, CryptoRng, RngCore};
 
+pub mod adversary;
+pub mod simulator;
+
+/// Generates integration tests that run test cases of this module with
+/// ciphersuite `$C` and random number generator `$rng`.
+///
+/// Test cases that depend on optional features are gated on features of the
+/// calling crate.
+#[macro_export]
+macro_rules! integration_tests {
+    ($C:ty, $rng:expr) => {
+        use $crate::tests::{
+            self, adversary,
+            simulator::{self, DeliveryOrder},
+        };
+
+        #[test]
+        fn test_basic() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_basic(2, 3, &mut rng)?;
+            tests::test_basic(67, 100, &mut rng)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_malicious() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_malicious(2, 3, 1, &mut rng)?;
+            tests::test_malicious(67, 100, 33, &mut rng)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_malicious_coordinator() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_malicious_coordinator(2, 3, &mut rng)?;
+            tests::test_malicious_coordinator(67, 100, &mut rng)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_equivocation() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_equivocation(2, 3, &mut rng)?;
+            tests::test_equivocation(67, 100, &mut rng)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_signer_debug() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_signer_debug(&mut rng)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_preprocess() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_preprocess(2, 3, 1, 3, &mut rng)?;
+            tests::test_preprocess(67, 100, 33, 3, &mut rng)?;
+            Ok(())
+        }
+
+        #[cfg(all(feature = "std", feature = "serialization"))]
+        #[test]
+        fn test_file_nonce_store() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_file_nonce_store(&mut rng)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_reused_signing_commitments() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_reused_signing_commitments(&mut rng)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_unknown_identifier() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_unknown_identifier(&mut rng)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_liveness() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_liveness(&mut rng)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_introspection() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_introspection(&mut rng)?;
+            Ok(())
+        }
+
+        #[cfg(feature = "std")]
+        #[test]
+        fn test_metrics() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_metrics(2, 5, 1, &mut rng)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_transcript() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_transcript(2, 3, &mut rng)?;
+            tests::test_transcript(5, 10, &mut rng)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_adversary() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            adversary::test_adversary(3, 5, 2, adversary::WithholdShares, &mut rng)?;
+            adversary::test_adversary(3, 5, 2, adversary::GarbageShares, &mut rng)?;
+            adversary::test_adversary(3, 5, 2, adversary::ReplayCommitments, &mut rng)?;
+            adversary::test_adversary(3, 5, 2, adversary::Crash, &mut rng)?;
+            adversary::test_adversary(3, 5, 2, adversary::UnsolicitedReply, &mut rng)?;
+            adversary::test_adversary(3, 5, 2, adversary::Equivocate, &mut rng)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_simulation() {
+            for delivery_order in [
+                DeliveryOrder::Random,
+                DeliveryOrder::Delayed { max_delay: 8 },
+                DeliveryOrder::Adversarial,
+            ] {
+                simulator::test_simulation::<$C, _>(
+                    3,
+                    5,
+                    2,
+                    adversary::WithholdShares,
+                    delivery_order,
+                    0..8,
+                );
+                simulator::test_simulation::<$C, _>(
+                    3,
+                    5,
+                    2,
+                    adversary::GarbageShares,
+                    delivery_order,
+                    0..8,
+                );
+                simulator::test_simulation::<$C, _>(
+                    3,
+                    5,
+                    2,
+                    adversary::Equivocate,
+                    delivery_order,
+                    0..8,
+                );
+            }
+        }
+
+        #[test]
+        fn test_receive_batch() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_receive_batch(2, 3, &mut rng)?;
+            tests::test_receive_batch(5, 10, &mut rng)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_receive_batch_session_change() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_receive_batch_session_change(2, 3, &mut rng)?;
+            tests::test_receive_batch_session_change(5, 10, &mut rng)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_receive_many() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_receive_many(2, 3, &mut rng)?;
+            tests::test_receive_many(5, 10, &mut rng)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_envelope() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_envelope(2, 3, &mut rng)?;
+            tests::test_envelope(5, 10, &mut rng)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_compact() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_compact(2, 3, &mut rng)?;
+            tests::test_compact(5, 10, &mut rng)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_run_id() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_run_id(2, 3, &mut rng)?;
+            tests::test_run_id(5, 10, &mut rng)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_authentication() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_authentication(2, 3, &mut rng)?;
+            tests::test_authentication(5, 10, &mut rng)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_multi_coordinator() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_multi_coordinator(2, 3, &mut rng)?;
+            tests::test_multi_coordinator(5, 10, &mut rng)?;
+            Ok(())
+        }
+
+        #[cfg(feature = "protobuf")]
+        #[test]
+        fn test_protobuf() -> Result<(), $crate::Error<$C>> {
+            let mut rng = $rng;
+            tests::test_protobuf(2, 3, &mut rng)?;
+            tests::test_protobuf(5, 10, &mut rng)?;
+            Ok(())
+        }
+    };
+}
+
 /// Runs ROAST algorithm with `min_signers`/`max_signers` multi-signature and no
 /// malicious signers.
 pub fn test_basic<C: Ciphersuite, RNG: RngCore + CryptoRng>(
@@ -29,20 +275,59 @@ pub fn test_malicious<C: Ciphersuite, RNG: RngCore + CryptoRng>(
     malicious_signers: u16,
     rng: &mut RNG,
 ) -> Result<(), Error<C>> {
+    test_preprocess(min_signers, max_signers, malicious_signers, 0, rng)
+}
+
+/// Runs ROAST algorithm with `min_signers`/`max_signers` multi-signature and
+/// `malicious_signers`, where each signer publishes `preprocessed` additional
+/// [`SigningCommitments`](frost_core::round1::SigningCommitments) upfront.
+pub fn test_preprocess<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    min_signers: u16,
+    max_signers: u16,
+    malicious_signers: u16,
+    preprocessed: usize,
+    rng: &mut RNG,
+) -> Result<(), Error<C>> {
+    run(
+        min_signers,
+        max_signers,
+        malicious_signers,
+        preprocessed,
+        (),
+        rng,
+    )?;
+
+    Ok(())
+}
+
+/// Runs ROAST algorithm and returns [`Coordinator`] with the number of started
+/// sessions.
+fn run<C: Ciphersuite, M: Metrics, RNG: RngCore + CryptoRng>(
+    min_signers: u16,
+    max_signers: u16,
+    malicious_signers: u16,
+    preprocessed: usize,
+    metrics: M,
+    rng: &mut RNG,
+) -> Result<(Coordinator<C, M>, u16), Error<C>> {
     let (secret_shares, public_key_package) =
         keys::generate_with_dealer(max_signers, min_signers, IdentifierList::Default, rng)?;
 
-    let mut coordinator = Coordinator::new(
+    let message = b"message to sign".to_vec();
+    let mut coordinator = Coordinator::with_metrics(
         max_signers,
         min_signers,
         public_key_package,
-        b"message to sign".into(),
+        message.clone(),
+        metrics,
     )?;
     let mut signers: BTreeMap<_, _> = BTreeMap::new();
 
     for (identifier, secret_share) in secret_shares {
         let key_package = KeyPackage::try_from(secret_share)?;
-        signers.insert(identifier, Signer::new(key_package, rng));
+        let mut signer = Signer::new(key_package, message.clone(), rng)?;
+        coordinator.preprocess(identifier, signer.preprocess(preprocessed, rng)?)?;
+        signers.insert(identifier, signer);
     }
 
     assert!(malicious_signers <= max_signers - min_signers);
@@ -64,10 +349,20 @@ pub fn test_malicious<C: Ciphersuite, RNG: RngCore + CryptoRng>(
                     if is_malicious {
                         let zero = <<C::Group as Group>::Field as Field>::zero();
                         let serialization = <<C::Group as Group>::Field as Field>::serialize(&zero);
-                        signer.regenerate_signing_nonces(rng);
+                        signer.regenerate_signing_nonces(rng).unwrap();
                         Some(SignatureShare::<C>::deserialize(serialization.as_ref()).unwrap())
                     } else {
-                        signer.receive(signing_package, rng).ok()
+                        let signing_commitments = signer.signing_commitments();
+                        let signature_share = signer.receive(signing_package, rng).ok()?;
+
+                        // Current signing commitments are replaced only once
+                        // used, so unused signing nonces do not pile up.
+                        assert_eq!(
+                            signer.signing_commitments() == signing_commitments,
+                            signing_package.signing_commitments()[&identifier]
+                                != signing_commitments
+                        );
+                        Some(signature_share)
                     }
                 });
             match coordinator.receive(identifier, signature_share, signer.signing_commitments()) {
@@ -93,5 +388,2151 @@ pub fn test_malicious<C: Ciphersuite, RNG: RngCore + CryptoRng>(
 
     assert!(session_counter <= max_signers - min_signers + 1);
 
+    Ok((coordinator, session_counter))
+}
+
+/// Runs signer-side validation of [`SigningPackage`] with
+/// `min_signers`/`max_signers` multi-signature and a malicious coordinator.
+pub fn test_malicious_coordinator<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    min_signers: u16,
+    max_signers: u16,
+    rng: &mut RNG,
+) -> Result<(), Error<C>> {
+    assert!(min_signers < max_signers);
+
+    let (secret_shares, _) =
+        keys::generate_with_dealer(max_signers, min_signers, IdentifierList::Default, rng)?;
+
+    let message = b"message to sign".to_vec();
+    let mut signers: BTreeMap<_, _> = BTreeMap::new();
+
+    for (identifier, secret_share) in secret_shares {
+        let key_package = KeyPackage::try_from(secret_share)?;
+        signers.insert(identifier, Signer::new(key_package, message.clone(), rng)?);
+    }
+
+    let mut identifiers = signers.keys().copied();
+    let identifier = identifiers.next().unwrap();
+    let other_identifier = identifiers.next().unwrap();
+    let outsider = identifiers.last().unwrap();
+
+    let signing_commitments: BTreeMap<_, _> = signers
+        .iter()
+        .take(min_signers as usize)
+        .map(|(identifier, signer)| (*identifier, signer.signing_commitments()))
+        .collect();
+    let signing_package = SigningPackage::new(signing_commitments.clone(), &message);
+
+    let outsider_signer = signers.get_mut(&outsider).unwrap();
+    let outsider_signing_commitments = outsider_signer.signing_commitments();
+    assert!(matches!(
+        outsider_signer.receive(&signing_package, rng),
+        Err(Error::MaliciousCoordinator(
+            MaliciousCoordinatorError::MissingSigningCommitments
+        ))
+    ));
+
+    let mut invalid_signing_commitments = signing_commitments.clone();
+    invalid_signing_commitments.insert(identifier, signing_commitments[&other_identifier]);
+    let mut too_many_signing_commitments = signing_commitments.clone();
+    too_many_signing_commitments.insert(outsider, outsider_signing_commitments);
+
+    let signer = signers.get_mut(&identifier).unwrap();
+    assert!(matches!(
+        signer.receive(
+            &SigningPackage::new(invalid_signing_commitments, &message),
+            rng
+        ),
+        Err(Error::MaliciousCoordinator(
+            MaliciousCoordinatorError::InvalidSigningCommitments
+        ))
+    ));
+    assert!(matches!(
+        signer.receive(
+            &SigningPackage::new(too_many_signing_commitments, &message),
+            rng
+        ),
+        Err(Error::MaliciousCoordinator(
+            MaliciousCoordinatorError::InvalidNumberOfSigningCommitments
+        ))
+    ));
+    assert!(matches!(
+        signer.receive(
+            &SigningPackage::new(signing_commitments, b"another message to sign"),
+            rng
+        ),
+        Err(Error::MaliciousCoordinator(
+            MaliciousCoordinatorError::InvalidMessage
+        ))
+    ));
+
+    signer.receive(&signing_package, rng)?;
+
+    Ok(())
+}
+
+/// Runs detection of coordinator equivocation with
+/// `min_signers`/`max_signers` multi-signature.
+pub fn test_equivocation<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    min_signers: u16,
+    max_signers: u16,
+    rng: &mut RNG,
+) -> Result<(), Error<C>> {
+    let (secret_shares, _) =
+        keys::generate_with_dealer(max_signers, min_signers, IdentifierList::Default, rng)?;
+
+    let message = b"message to sign".to_vec();
+    let mut signers: BTreeMap<_, _> = BTreeMap::new();
+
+    for (identifier, secret_share) in secret_shares.into_iter().take(min_signers as usize) {
+        let key_package = KeyPackage::try_from(secret_share)?;
+        signers.insert(identifier, Signer::new(key_package, message.clone(), rng)?);
+    }
+
+    let signing_commitments: BTreeMap<_, _> = signers
+        .iter()
+        .map(|(identifier, signer)| (*identifier, signer.signing_commitments()))
+        .collect();
+    let signing_package = SigningPackage::new(signing_commitments.clone(), &message);
+    let another_signing_package =
+        SigningPackage::new(signing_commitments, b"another message to sign");
+
+    let mut identifiers = signers.keys().copied();
+    let identifier = identifiers.next().unwrap();
+    let other_identifier = identifiers.next().unwrap();
+
+    let signer = signers.get_mut(&identifier).unwrap();
+
+    // Equivocation cannot be ruled out without digests of other signers.
+    let mut signing_package_digests = BTreeMap::new();
+    assert!(matches!(
+        signer.receive_with_digests(&signing_package, &signing_package_digests, rng),
+        Err(Error::MaliciousCoordinator(
+            MaliciousCoordinatorError::MissingSigningPackageDigest
+        ))
+    ));
+
+    for identifier in signing_package.signing_commitments().keys() {
+        signing_package_digests.insert(*identifier, SigningPackageDigest::new(&signing_package)?);
+    }
+    signing_package_digests.insert(
+        other_identifier,
+        SigningPackageDigest::new(&another_signing_package)?,
+    );
+    assert!(matches!(
+        signer.receive_with_digests(&signing_package, &signing_package_digests, rng),
+        Err(Error::MaliciousCoordinator(
+            MaliciousCoordinatorError::Equivocation
+        ))
+    ));
+
+    // Digest of the signer itself is not required.
+    signing_package_digests.insert(
+        other_identifier,
+        SigningPackageDigest::new(&signing_package)?,
+    );
+    signing_package_digests.remove(&identifier);
+    signer.receive_with_digests(&signing_package, &signing_package_digests, rng)?;
+
+    Ok(())
+}
+
+/// Checks that [`Signer`] does not expose secret material in its debug output.
+pub fn test_signer_debug<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    rng: &mut RNG,
+) -> Result<(), Error<C>> {
+    let (secret_shares, _) = keys::generate_with_dealer(3, 2, IdentifierList::Default, rng)?;
+    let secret_share = secret_shares.into_values().next().unwrap();
+    let key_package = KeyPackage::try_from(secret_share)?;
+    let mut secrets = vec![key_package.signing_share().serialize()];
+
+    let signer = Signer::new(key_package, b"message to sign".to_vec(), rng)?;
+    for signing_nonces in &signer.nonce_store().signing_nonces {
+        secrets.push(signing_nonces.hiding().serialize());
+        secrets.push(signing_nonces.binding().serialize());
+    }
+
+    let debug = format!("{signer:?}");
+    let lowercase_debug = debug.to_lowercase();
+    assert!(debug.contains("<redacted>"));
+
+    for secret in secrets {
+        // Neither hex nor byte array representation of the secret is exposed.
+        let hex: alloc::string::String = secret.iter().map(|byte| format!("{byte:02x}")).collect();
+        let bytes = format!("{secret:?}");
+        assert!(!lowercase_debug.contains(&hex));
+        assert!(!debug.contains(bytes.trim_start_matches('[').trim_end_matches(']')));
+    }
+
+    Ok(())
+}
+
+/// Checks that [`Signer`] with
+/// [`FileNonceStore`](crate::FileNonceStore) never signs twice with the same
+/// [`SigningNonces`](frost_core::round1::SigningNonces), even after restart.
+#[cfg(all(feature = "std", feature = "serialization"))]
+pub fn test_file_nonce_store<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    rng: &mut RNG,
+) -> Result<(), Error<C>> {
+    use crate::{FileNonceStore, NonceStoreError};
+
+    let (secret_shares, _) = keys::generate_with_dealer(3, 2, IdentifierList::Default, rng)?;
+
+    let message = b"message to sign".to_vec();
+    let mut key_packages: BTreeMap<_, _> = BTreeMap::new();
+
+    for (identifier, secret_share) in secret_shares {
+        key_packages.insert(identifier, KeyPackage::try_from(secret_share)?);
+    }
+
+    let mut identifiers = key_packages.keys().copied();
+    let identifier = identifiers.next().unwrap();
+    let other_identifier = identifiers.next().unwrap();
+
+    let mut path = std::env::temp_dir();
+    path.push(format!("roast-nonce-store-{}-{}", C::ID, rng.next_u64()));
+
+    let signer = Signer::with_nonce_store(
+        key_packages[&identifier].clone(),
+        message.clone(),
+        FileNonceStore::open(&path)?,
+        rng,
+    )?;
+    let signing_commitments = signer.signing_commitments();
+    drop(signer);
+
+    // Store file is readable only by its owner.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let metadata = std::fs::metadata(&path).map_err(NonceStoreError::from)?;
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+    }
+
+    let other_signer = Signer::new(
+        key_packages[&other_identifier].clone(),
+        message.clone(),
+        rng,
+    )?;
+
+    let mut signing_commitments_map = BTreeMap::new();
+    signing_commitments_map.insert(identifier, signing_commitments);
+    signing_commitments_map.insert(other_identifier, other_signer.signing_commitments());
+    let signing_package = SigningPackage::new(signing_commitments_map, &message);
+
+    let mut signer = Signer::with_nonce_store(
+        key_packages[&identifier].clone(),
+        message.clone(),
+        FileNonceStore::open(&path)?,
+        rng,
+    )?;
+    signer.receive(&signing_package, rng)?;
+    drop(signer);
+
+    let mut signer = Signer::with_nonce_store(
+        key_packages[&identifier].clone(),
+        message.clone(),
+        FileNonceStore::open(&path)?,
+        rng,
+    )?;
+    assert!(matches!(
+        signer.receive(&signing_package, rng),
+        Err(Error::MaliciousCoordinator(
+            MaliciousCoordinatorError::InvalidSigningCommitments
+        ))
+    ));
+    drop(signer);
+
+    // Truncated store file is rejected.
+    std::fs::write(&path, [0, 0, 0, 5, 1]).map_err(NonceStoreError::from)?;
+    assert!(matches!(
+        FileNonceStore::<C>::open(&path),
+        Err(Error::NonceStore(NonceStoreError::Malformed))
+    ));
+
+    std::fs::remove_file(&path).map_err(NonceStoreError::from)?;
+
+    Ok(())
+}
+
+/// Checks that coordinator marks signers that reuse their own
+/// [`SigningCommitments`](frost_core::round1::SigningCommitments) as
+/// malicious and keeps valid signature share of such reply, and that it marks
+/// signers that copy
+/// [`SigningCommitments`](frost_core::round1::SigningCommitments) of another
+/// signer as malicious.
+pub fn test_reused_signing_commitments<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    rng: &mut RNG,
+) -> Result<(), Error<C>> {
+    let (secret_shares, public_key_package) =
+        keys::generate_with_dealer(4, 2, IdentifierList::Default, rng)?;
+
+    let message = b"message to sign".to_vec();
+    let mut coordinator = Coordinator::new(4, 2, public_key_package, message.clone())?;
+    let mut signers: BTreeMap<_, _> = BTreeMap::new();
+
+    for (identifier, secret_share) in secret_shares {
+        let key_package = KeyPackage::try_from(secret_share)?;
+        signers.insert(identifier, Signer::new(key_package, message.clone(), rng)?);
+    }
+
+    let identifiers: Vec<_> = signers.keys().copied().collect();
+    let signing_commitments = signers[&identifiers[1]].signing_commitments();
+
+    // Malicious signer copies signing commitments of honest signer after the
+    // honest signer has sent them.
+    assert!(matches!(
+        coordinator.receive(identifiers[1], None, signing_commitments),
+        Ok(SessionStatus::InProgress)
+    ));
+    assert!(matches!(
+        coordinator.receive(identifiers[0], None, signing_commitments),
+        Err(Error::MaliciousSigner(
+            MaliciousSignerError::CopiedSigningCommitments
+        ))
+    ));
+    assert_eq!(
+        coordinator.malicious_signers().get(&identifiers[0]),
+        Some(&MaliciousSignerError::CopiedSigningCommitments)
+    );
+
+    let SessionStatus::Started {
+        signers: ids,
+        signing_package,
+    } = coordinator.receive(
+        identifiers[2],
+        None,
+        signers[&identifiers[2]].signing_commitments(),
+    )?
+    else {
+        panic!("session is not started");
+    };
+    assert!(ids.contains(&identifiers[1]) && !ids.contains(&identifiers[0]));
+
+    // Honest signer signs, but reuses its signing commitments.
+    let signer = signers.get_mut(&identifiers[1]).unwrap();
+    let signature_share = signer.receive(&signing_package, rng)?;
+    assert!(matches!(
+        coordinator.receive(identifiers[1], Some(signature_share), signing_commitments),
+        Err(Error::MaliciousSigner(
+            MaliciousSignerError::ReusedSigningCommitments
+        ))
+    ));
+    assert!(coordinator
+        .session(1)
+        .unwrap()
+        .signature_shares()
+        .contains_key(&identifiers[1]));
+    assert_eq!(coordinator.malicious_signers().len(), 2);
+
+    Ok(())
+}
+
+/// Checks that coordinator rejects replies of signers that are not in
+/// [`PublicKeyPackage`](frost_core::keys::PublicKeyPackage) without marking
+/// anyone as malicious or starting sessions with them.
+pub fn test_unknown_identifier<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    rng: &mut RNG,
+) -> Result<(), Error<C>> {
+    let (secret_shares, public_key_package) =
+        keys::generate_with_dealer(4, 2, IdentifierList::Default, rng)?;
+
+    let message = b"message to sign".to_vec();
+    let mut coordinator = Coordinator::new(4, 2, public_key_package, message.clone())?;
+    let mut signers: BTreeMap<_, _> = BTreeMap::new();
+
+    for (identifier, secret_share) in secret_shares {
+        let key_package = KeyPackage::try_from(secret_share)?;
+        signers.insert(identifier, Signer::new(key_package, message.clone(), rng)?);
+    }
+
+    for (index, signer) in (100u16..104).zip(signers.values()) {
+        let identifier = Identifier::try_from(index)?;
+        assert!(matches!(
+            coordinator.receive(identifier, None, signer.signing_commitments()),
+            Err(Error::Frost(frost_core::Error::UnknownIdentifier))
+        ));
+        assert_eq!(
+            coordinator.preprocess(identifier, vec![signer.signing_commitments()]),
+            Err(Error::Frost(frost_core::Error::UnknownIdentifier))
+        );
+    }
+    assert!(coordinator.malicious_signers().is_empty());
+    assert_eq!(coordinator.session_count(), 0);
+
+    // Signing commitments of unknown signers are not registered, so honest
+    // signers can still send them.
+    let mut session_status = SessionStatus::InProgress;
+    for (identifier, signer) in &signers {
+        if matches!(session_status, SessionStatus::InProgress) {
+            session_status =
+                coordinator.receive(*identifier, None, signer.signing_commitments())?;
+        }
+    }
+    let SessionStatus::Started { signers: ids, .. } = session_status else {
+        panic!("session is not started");
+    };
+    assert!(ids
+        .iter()
+        .all(|identifier| signers.contains_key(identifier)));
+    assert!(coordinator.malicious_signers().is_empty());
+
+    Ok(())
+}
+
+/// Checks that [`LivenessCoordinator`] reports and marks as unresponsive
+/// signers that have not replied within deadline, using a fake clock.
+pub fn test_liveness<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    rng: &mut RNG,
+) -> Result<(), Error<C>> {
+    let (secret_shares, public_key_package) =
+        keys::generate_with_dealer(3, 2, IdentifierList::Default, rng)?;
+
+    let message = b"message to sign".to_vec();
+    let coordinator = Coordinator::new(3, 2, public_key_package, message.clone())?;
+    let mut coordinator = LivenessCoordinator::new(
+        coordinator,
+        Duration::from_secs(10),
+        TimeoutPolicy::MarkUnresponsive,
+    );
+    let mut signers: BTreeMap<_, _> = BTreeMap::new();
+
+    for (identifier, secret_share) in secret_shares {
+        let key_package = KeyPackage::try_from(secret_share)?;
+        signers.insert(identifier, Signer::new(key_package, message.clone(), rng)?);
+    }
+
+    let identifiers: Vec<_> = signers.keys().copied().collect();
+
+    coordinator.receive(
+        Duration::ZERO,
+        identifiers[0],
+        None,
+        signers[&identifiers[0]].signing_commitments(),
+    )?;
+    let SessionStatus::Started {
+        signing_package, ..
+    } = coordinator.receive(
+        Duration::ZERO,
+        identifiers[1],
+        None,
+        signers[&identifiers[1]].signing_commitments(),
+    )?
+    else {
+        panic!("session is not started");
+    };
+
+    let signer = signers.get_mut(&identifiers[0]).unwrap();
+    let signature_share = signer.receive(&signing_package, rng)?;
+    coordinator.receive(
+        Duration::from_secs(5),
+        identifiers[0],
+        Some(signature_share),
+        signer.signing_commitments(),
+    )?;
+
+    assert!(coordinator
+        .tick(Duration::from_secs(9))
+        .map_err(|err| err.error)?
+        .is_empty());
+
+    let timeout_reports = coordinator
+        .tick(Duration::from_secs(10))
+        .map_err(|err| err.error)?;
+    assert_eq!(timeout_reports.len(), 1);
+    assert_eq!(timeout_reports[0].identifier, identifiers[1]);
+    assert!(coordinator
+        .tick(Duration::from_secs(11))
+        .map_err(|err| err.error)?
+        .is_empty());
+
+    assert!(matches!(
+        coordinator.receive(
+            Duration::from_secs(12),
+            identifiers[1],
+            None,
+            signers[&identifiers[1]].signing_commitments(),
+        ),
+        Err(Error::MaliciousSigner(MaliciousSignerError::Unresponsive))
+    ));
+
+    let SessionStatus::Started {
+        signing_package, ..
+    } = coordinator.receive(
+        Duration::from_secs(12),
+        identifiers[2],
+        None,
+        signers[&identifiers[2]].signing_commitments(),
+    )?
+    else {
+        panic!("session is not started");
+    };
+
+    for identifier in [identifiers[0], identifiers[2]] {
+        let signer = signers.get_mut(&identifier).unwrap();
+        let signature_share = signer.receive(&signing_package, rng)?;
+        let session_status = coordinator.receive(
+            Duration::from_secs(13),
+            identifier,
+            Some(signature_share),
+            signer.signing_commitments(),
+        )?;
+        if identifier == identifiers[2] {
+            assert!(matches!(session_status, SessionStatus::Finished { .. }));
+        }
+    }
+
+    // All signers of the session are reported even if marking them exceeds
+    // the number of tolerated malicious signers.
+    let (secret_shares, public_key_package) =
+        keys::generate_with_dealer(3, 2, IdentifierList::Default, rng)?;
+    let coordinator = Coordinator::new(3, 2, public_key_package, message.clone())?;
+    let mut coordinator = LivenessCoordinator::new(
+        coordinator,
+        Duration::from_secs(10),
+        TimeoutPolicy::MarkUnresponsive,
+    );
+
+    let mut session_signers = BTreeSet::new();
+    for (identifier, secret_share) in secret_shares {
+        let key_package = KeyPackage::try_from(secret_share)?;
+        let mut signer = Signer::new(key_package, message.clone(), rng)?;
+        coordinator.preprocess(identifier, signer.preprocess(1, rng)?)?;
+        if let SessionStatus::Started { signers, .. } =
+            coordinator.receive_envelope(Duration::ZERO, signer.envelope())?
+        {
+            session_signers = signers;
+        }
+    }
+
+    let err = coordinator.tick(Duration::from_secs(10)).unwrap_err();
+    assert_eq!(err.error, Error::TooManyMaliciousSigners);
+    assert_eq!(
+        err.reports
+            .iter()
+            .map(|timeout_report| timeout_report.identifier)
+            .collect::<BTreeSet<_>>(),
+        session_signers
+    );
+
+    Ok(())
+}
+
+/// Checks read-only view of [`Coordinator`] state during the run.
+pub fn test_introspection<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    rng: &mut RNG,
+) -> Result<(), Error<C>> {
+    let (secret_shares, public_key_package) =
+        keys::generate_with_dealer(3, 2, IdentifierList::Default, rng)?;
+
+    let message = b"message to sign".to_vec();
+    let mut coordinator = Coordinator::new(3, 2, public_key_package, message.clone())?;
+    let mut signers: BTreeMap<_, _> = BTreeMap::new();
+
+    for (identifier, secret_share) in secret_shares {
+        let key_package = KeyPackage::try_from(secret_share)?;
+        signers.insert(identifier, Signer::new(key_package, message.clone(), rng)?);
+    }
+
+    let identifiers: Vec<_> = signers.keys().copied().collect();
+
+    assert_eq!(coordinator.message(), &message[..]);
+    assert_eq!(coordinator.session_count(), 0);
+    assert_eq!(coordinator.remaining_malicious_tolerance(), 1);
+    assert_eq!(
+        coordinator.signer_status(&identifiers[0]),
+        SignerStatus::Unknown
+    );
+
+    coordinator.receive(
+        identifiers[0],
+        None,
+        signers[&identifiers[0]].signing_commitments(),
+    )?;
+    assert_eq!(
+        coordinator.signer_status(&identifiers[0]),
+        SignerStatus::Responsive
+    );
+
+    let SessionStatus::Started {
+        signing_package, ..
+    } = coordinator.receive(
+        identifiers[1],
+        None,
+        signers[&identifiers[1]].signing_commitments(),
+    )?
+    else {
+        panic!("session is not started");
+    };
+    assert_eq!(coordinator.session_count(), 1);
+    assert!(coordinator.responsive_signers().is_empty());
+
+    for identifier in &identifiers[..2] {
+        assert_eq!(
+            coordinator.signer_status(identifier),
+            SignerStatus::InSession(1)
+        );
+    }
+
+    let signer = signers.get_mut(&identifiers[0]).unwrap();
+    let signature_share = signer.receive(&signing_package, rng)?;
+    coordinator.receive(
+        identifiers[0],
+        Some(signature_share),
+        signer.signing_commitments(),
+    )?;
+
+    let session_info = coordinator.open_sessions().next().unwrap();
+    assert_eq!(session_info.session_id(), 1);
+    assert_eq!(session_info.signing_package(), &signing_package);
+    assert_eq!(session_info.signers().count(), 2);
+    assert!(session_info
+        .signature_shares()
+        .contains_key(&identifiers[0]));
+    assert_eq!(
+        session_info.missing_signers().collect::<Vec<_>>(),
+        vec![identifiers[1]]
+    );
+
+    assert!(coordinator
+        .receive(
+            identifiers[0],
+            None,
+            signers[&identifiers[0]].signing_commitments(),
+        )
+        .is_err());
+    assert_eq!(
+        coordinator.signer_status(&identifiers[0]),
+        SignerStatus::Malicious(MaliciousSignerError::UnsolicitedReply)
+    );
+    assert_eq!(coordinator.malicious_signers().len(), 1);
+    assert_eq!(coordinator.remaining_malicious_tolerance(), 0);
+
+    Ok(())
+}
+
+/// Runs ROAST algorithm with `min_signers`/`max_signers` multi-signature and
+/// `malicious_signers` and checks metrics recorded by
+/// [`InMemoryMetrics`](crate::InMemoryMetrics).
+#[cfg(feature = "std")]
+pub fn test_metrics<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    min_signers: u16,
+    max_signers: u16,
+    malicious_signers: u16,
+    rng: &mut RNG,
+) -> Result<(), Error<C>> {
+    use crate::InMemoryMetrics;
+
+    let mut metrics = InMemoryMetrics::new();
+
+    for _ in 0..2 {
+        run(
+            min_signers,
+            max_signers,
+            malicious_signers,
+            0,
+            &mut metrics,
+            rng,
+        )?;
+    }
+
+    let sessions_per_run = metrics.sessions_per_run();
+    assert_eq!(sessions_per_run.len(), 2);
+    assert_eq!(metrics.time_to_signature().len(), 2);
+    assert_eq!(
+        metrics.sessions_started(),
+        sessions_per_run
+            .iter()
+            .map(|sessions| *sessions as u64)
+            .sum()
+    );
+    assert!(metrics.signature_shares_verified() >= 2 * min_signers as u64);
+
+    let invalid_signature_shares = metrics
+        .malicious_signers()
+        .get(&MaliciousSignerError::InvalidSignatureShare)
+        .copied()
+        .unwrap_or_default();
+    assert!(invalid_signature_shares <= 2 * malicious_signers as u64);
+    assert_eq!(
+        metrics.malicious_signers().values().sum::<u64>(),
+        invalid_signature_shares
+    );
+
+    Ok(())
+}
+
+/// Runs ROAST algorithm with `min_signers`/`max_signers` multi-signature and
+/// one malicious signer through [`TranscriptRecorder`] and checks that
+/// recorded [`Transcript`] replays to the same outputs, including runs with
+/// custom run id, envelopes, signed envelopes and batches.
+pub fn test_transcript<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    min_signers: u16,
+    max_signers: u16,
+    rng: &mut RNG,
+) -> Result<(), Error<C>>
+where
+    Identifier<C>: Sync,
+    SignatureShare<C>: Sync,
+    SigningPackage<C>: Sync,
+    VerifyingShare<C>: Sync,
+    VerifyingKey<C>: Sync,
+{
+    let (transcript, malicious_identifier) = record(min_signers, max_signers, rng)?;
+    assert!(transcript.entries()[..max_signers as usize]
+        .iter()
+        .all(|entry| matches!(entry.input, TranscriptInput::Preprocess { .. })));
+    assert!(transcript.entries().iter().any(|entry| entry.output
+        == TranscriptOutput::Error(Error::MaliciousSigner(
+            MaliciousSignerError::InvalidSignatureShare
+        ))));
+
+    let coordinator = transcript.replay()?;
+    assert!(coordinator
+        .malicious_signers()
+        .contains_key(&malicious_identifier));
+
+    #[cfg(feature = "serialization")]
+    {
+        let serialization = transcript.serialize()?;
+        let deserialized = Transcript::deserialize(&serialization)?;
+        assert_eq!(deserialized, transcript);
+        deserialized.replay()?;
+
+        assert_eq!(
+            Transcript::<C>::deserialize(&serialization[..serialization.len() - 1]),
+            Err(Error::InvalidTranscript)
+        );
+    }
+
+    for signed in [false, true] {
+        let (transcript, run_id) = record_envelopes(min_signers, max_signers, signed, rng)?;
+        assert_eq!(transcript.run_id(), run_id);
+        assert_eq!(transcript.identity_keys().is_empty(), !signed);
+        assert!(transcript.entries().iter().any(|entry| match entry.input {
+            TranscriptInput::ReceiveBatch { .. } => !signed,
+            TranscriptInput::ReceiveSignedEnvelope { .. } => signed,
+            TranscriptInput::ReceiveEnvelope { .. } => !signed,
+            _ => false,
+        }));
+        assert!(transcript
+            .entries()
+            .iter()
+            .any(|entry| matches!(entry.output, TranscriptOutput::Finished { .. })));
+
+        let coordinator = transcript.replay()?;
+        assert_eq!(coordinator.run_id(), run_id);
+
+        #[cfg(feature = "serialization")]
+        {
+            let deserialized = Transcript::deserialize(&transcript.serialize()?)?;
+            assert_eq!(deserialized, transcript);
+            deserialized.replay()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs ROAST algorithm with `min_signers`/`max_signers` multi-signature and
+/// custom run id through [`TranscriptRecorder`] with signed envelopes if
+/// `signed` or with a batch of first replies and envelopes otherwise. Returns
+/// recorded [`Transcript`] with the run id.
+fn record_envelopes<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    min_signers: u16,
+    max_signers: u16,
+    signed: bool,
+    rng: &mut RNG,
+) -> Result<(Transcript<C>, RunId), Error<C>>
+where
+    Identifier<C>: Sync,
+    SignatureShare<C>: Sync,
+    SigningPackage<C>: Sync,
+    VerifyingShare<C>: Sync,
+    VerifyingKey<C>: Sync,
+{
+    assert!(min_signers < max_signers);
+
+    let (secret_shares, public_key_package) =
+        keys::generate_with_dealer(max_signers, min_signers, IdentifierList::Default, rng)?;
+
+    let mut run_id = [0; 32];
+    rng.fill_bytes(&mut run_id);
+    let run_id = RunId::new(run_id);
+
+    let message = b"message to sign".to_vec();
+    let mut signers: BTreeMap<_, _> = BTreeMap::new();
+    let mut identity_keys: BTreeMap<_, _> = BTreeMap::new();
+    for (identifier, secret_share) in secret_shares {
+        let key_package = KeyPackage::try_from(secret_share)?;
+        let signer = Signer::new(key_package, message.clone(), rng)?.with_run_id(run_id);
+        let signing_key = SigningKey::<C>::new(rng);
+        if signed {
+            identity_keys.insert(identifier, VerifyingKey::from(&signing_key));
+        }
+        signers.insert(identifier, (signer, signing_key));
+    }
+
+    let mut recorder = TranscriptRecorder::new(
+        max_signers,
+        min_signers,
+        public_key_package,
+        message.clone(),
+    )?
+    .with_run_id(run_id)
+    .with_identity_keys(identity_keys);
+
+    let mut pending: Vec<_> = signers
+        .values()
+        .map(|(signer, _)| signer.envelope())
+        .collect();
+    if !signed {
+        let replies = pending
+            .drain(..)
+            .map(|envelope| match *envelope.payload() {
+                Payload::Reply {
+                    identifier,
+                    signature_share,
+                    signing_commitments,
+                } => (identifier, signature_share, signing_commitments),
+                Payload::SigningPackage(_) => unreachable!(),
+            })
+            .collect();
+        let mut session_id = 0;
+        for result in recorder.receive_batch(replies) {
+            if let SessionStatus::Started { signers: ids, .. } = result? {
+                session_id += 1;
+                let envelope = recorder.coordinator().envelope(session_id).unwrap();
+                for identifier in ids {
+                    let (signer, _) = signers.get_mut(&identifier).unwrap();
+                    pending.push(signer.receive_envelope(&envelope, rng)?);
+                }
+            }
+        }
+    }
+
+    loop {
+        let envelope = pending.remove(0);
+        let result = if signed {
+            let (_, signing_key) = match *envelope.payload() {
+                Payload::Reply { identifier, .. } => &signers[&identifier],
+                Payload::SigningPackage(_) => unreachable!(),
+            };
+            let signed_envelope = SignedEnvelope::sign(envelope, signing_key, rng)?;
+            recorder.receive_signed_envelope(signed_envelope)
+        } else {
+            recorder.receive_envelope(envelope)
+        };
+
+        match result {
+            Ok(SessionStatus::InProgress) => {}
+            Ok(SessionStatus::Started { signers: ids, .. }) => {
+                let session_id = recorder.coordinator().session_count();
+                let envelope = recorder.coordinator().envelope(session_id).unwrap();
+                for identifier in ids {
+                    let (signer, _) = signers.get_mut(&identifier).unwrap();
+                    pending.push(signer.receive_envelope(&envelope, rng)?);
+                }
+            }
+            Ok(SessionStatus::Finished { .. }) => break,
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok((recorder.into_transcript(), run_id))
+}
+
+/// Runs ROAST algorithm with `min_signers`/`max_signers` multi-signature and
+/// one malicious signer through [`TranscriptRecorder`] and returns recorded
+/// [`Transcript`] with identifier of malicious signer.
+fn record<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    min_signers: u16,
+    max_signers: u16,
+    rng: &mut RNG,
+) -> Result<(Transcript<C>, Identifier<C>), Error<C>> {
+    assert!(min_signers < max_signers);
+
+    let (secret_shares, public_key_package) =
+        keys::generate_with_dealer(max_signers, min_signers, IdentifierList::Default, rng)?;
+
+    let message = b"message to sign".to_vec();
+    let mut recorder = TranscriptRecorder::new(
+        max_signers,
+        min_signers,
+        public_key_package,
+        message.clone(),
+    )?;
+    let mut signers: BTreeMap<_, _> = BTreeMap::new();
+
+    for (identifier, secret_share) in secret_shares {
+        let key_package = KeyPackage::try_from(secret_share)?;
+        let mut signer = Signer::new(key_package, message.clone(), rng)?;
+        recorder.preprocess(identifier, signer.preprocess(1, rng)?)?;
+        signers.insert(identifier, signer);
+    }
+
+    let malicious_identifier = *signers.keys().next().unwrap();
+    let mut signing_packages: BTreeMap<_, _> = BTreeMap::new();
+
+    'outer: loop {
+        for (identifier, signer) in signers.iter_mut() {
+            let signature_share = signing_packages
+                .get(identifier)
+                .and_then(|signing_package| {
+                    if *identifier == malicious_identifier {
+                        let zero = <<C::Group as Group>::Field as Field>::zero();
+                        let serialization = <<C::Group as Group>::Field as Field>::serialize(&zero);
+                        signer.regenerate_signing_nonces(rng).unwrap();
+                        Some(SignatureShare::<C>::deserialize(serialization.as_ref()).unwrap())
+                    } else {
+                        signer.receive(signing_package, rng).ok()
+                    }
+                });
+            match recorder.receive(*identifier, signature_share, signer.signing_commitments()) {
+                Ok(SessionStatus::InProgress) => {}
+                Ok(SessionStatus::Started {
+                    signers,
+                    signing_package,
+                }) => {
+                    for signer in signers {
+                        signing_packages.insert(signer, signing_package.clone());
+                    }
+                }
+                Ok(SessionStatus::Finished { .. }) => break 'outer,
+                Err(Error::MaliciousSigner(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    Ok((recorder.into_transcript(), malicious_identifier))
+}
+
+/// Runs recorded ROAST algorithm with `min_signers`/`max_signers`
+/// multi-signature through [`Coordinator::receive`] and
+/// [`Coordinator::receive_batch`] and checks that results are the same.
+pub fn test_receive_batch<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    min_signers: u16,
+    max_signers: u16,
+    rng: &mut RNG,
+) -> Result<(), Error<C>>
+where
+    Identifier<C>: Sync,
+    SignatureShare<C>: Sync,
+    SigningPackage<C>: Sync,
+    VerifyingShare<C>: Sync,
+    VerifyingKey<C>: Sync,
+{
+    let (transcript, _) = record(min_signers, max_signers, rng)?;
+
+    let new_coordinator = || {
+        Coordinator::<C>::new(
+            transcript.max_signers(),
+            transcript.min_signers(),
+            transcript.public_key_package().clone(),
+            transcript.message().to_vec(),
+        )
+    };
+    let mut coordinator = new_coordinator()?;
+    let mut batch_coordinator = new_coordinator()?;
+
+    let mut replies = Vec::new();
+    for entry in transcript.entries() {
+        match entry.input.clone() {
+            TranscriptInput::Receive {
+                identifier,
+                signature_share,
+                signing_commitments,
+            } => replies.push((identifier, signature_share, signing_commitments)),
+            TranscriptInput::Preprocess {
+                identifier,
+                signing_commitments,
+            } => {
+                coordinator.preprocess(identifier, signing_commitments.clone())?;
+                batch_coordinator.preprocess(identifier, signing_commitments)?;
+            }
+            TranscriptInput::ReceiveEnvelope { .. }
+            | TranscriptInput::ReceiveSignedEnvelope { .. }
+            | TranscriptInput::ReceiveBatch { .. } => unreachable!(),
+        }
+    }
+
+    let mut outputs = Vec::new();
+    let mut batch_outputs = Vec::new();
+
+    while !replies.is_empty() {
+        let batch_size = (rng.next_u32() as usize % max_signers as usize + 1).min(replies.len());
+        let batch: Vec<_> = replies.drain(..batch_size).collect();
+
+        for (identifier, signature_share, signing_commitments) in batch.iter().copied() {
+            outputs.push(TranscriptOutput::from(&coordinator.receive(
+                identifier,
+                signature_share,
+                signing_commitments,
+            )));
+        }
+
+        batch_outputs.extend(
+            batch_coordinator
+                .receive_batch(batch)
+                .iter()
+                .map(TranscriptOutput::from),
+        );
+    }
+
+    assert_eq!(outputs, batch_outputs);
+    assert_eq!(
+        coordinator.malicious_signers(),
+        batch_coordinator.malicious_signers()
+    );
+    assert_eq!(
+        coordinator.responsive_signers(),
+        batch_coordinator.responsive_signers()
+    );
+    assert_eq!(
+        coordinator.session_count(),
+        batch_coordinator.session_count()
+    );
+
+    Ok(())
+}
+
+/// Runs [`Coordinator::receive_batch`] with `min_signers`/`max_signers`
+/// multi-signature on batches in which replies of other signers start a new
+/// session of a signer before its signature share, and checks that results are
+/// the same as for [`Coordinator::receive`].
+pub fn test_receive_batch_session_change<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    min_signers: u16,
+    max_signers: u16,
+    rng: &mut RNG,
+) -> Result<(), Error<C>>
+where
+    Identifier<C>: Sync,
+    SignatureShare<C>: Sync,
+    SigningPackage<C>: Sync,
+    VerifyingShare<C>: Sync,
+    VerifyingKey<C>: Sync,
+{
+    assert!(1 < min_signers && 2 * min_signers - 1 <= max_signers);
+
+    let (secret_shares, public_key_package) =
+        keys::generate_with_dealer(max_signers, min_signers, IdentifierList::Default, rng)?;
+
+    let message = b"message to sign".to_vec();
+    let mut signers = BTreeMap::new();
+    for (identifier, secret_share) in secret_shares {
+        let key_package = KeyPackage::try_from(secret_share)?;
+        signers.insert(identifier, Signer::new(key_package, message.clone(), rng)?);
+    }
+    let identifiers: Vec<_> = signers.keys().copied().collect();
+    let identifier = identifiers[0];
+
+    // The first `min_signers` signers start the first session and the signer
+    // signs it, so the signer becomes responsive again while the session is
+    // not finished yet.
+    let mut replies: Vec<_> = identifiers[..min_signers as usize]
+        .iter()
+        .map(|identifier| (*identifier, None, signers[identifier].signing_commitments()))
+        .collect();
+    let mut coordinator = Coordinator::<C>::new(
+        max_signers,
+        min_signers,
+        public_key_package.clone(),
+        message.clone(),
+    )?;
+    let mut signing_package = None;
+    for (identifier, signature_share, signing_commitments) in replies.iter().copied() {
+        if let SessionStatus::Started {
+            signing_package: package,
+            ..
+        } = coordinator.receive(identifier, signature_share, signing_commitments)?
+        {
+            signing_package = Some(package);
+        }
+    }
+    let signer = signers.get_mut(&identifier).unwrap();
+    let signature_share = signer.receive(&signing_package.unwrap(), rng)?;
+    let replayed_reply = (
+        identifier,
+        Some(signature_share),
+        signer.signing_commitments(),
+    );
+    replies.push(replayed_reply);
+
+    // Replies of the next `min_signers - 1` signers start the second session
+    // with the signer.
+    let new_replies: Vec<_> = identifiers[min_signers as usize..(2 * min_signers - 1) as usize]
+        .iter()
+        .map(|identifier| (*identifier, None, signers[identifier].signing_commitments()))
+        .collect();
+
+    let new_coordinator = || -> Result<Coordinator<C>, Error<C>> {
+        let mut coordinator = Coordinator::new(
+            max_signers,
+            min_signers,
+            public_key_package.clone(),
+            message.clone(),
+        )?;
+        for (identifier, signature_share, signing_commitments) in replies.iter().copied() {
+            coordinator.receive(identifier, signature_share, signing_commitments)?;
+        }
+        Ok(coordinator)
+    };
+
+    let mut coordinator = new_coordinator()?;
+    let mut signing_package = None;
+    for (identifier, signature_share, signing_commitments) in new_replies.iter().copied() {
+        if let SessionStatus::Started {
+            signing_package: package,
+            ..
+        } = coordinator.receive(identifier, signature_share, signing_commitments)?
+        {
+            signing_package = Some(package);
+        }
+    }
+    let signer = signers.get_mut(&identifier).unwrap();
+    let signature_share = signer.receive(&signing_package.unwrap(), rng)?;
+    let reply = (
+        identifier,
+        Some(signature_share),
+        signer.signing_commitments(),
+    );
+
+    for (reply, expected) in [
+        (
+            replayed_reply,
+            TranscriptOutput::Error(Error::MaliciousSigner(
+                MaliciousSignerError::InvalidSignatureShare,
+            )),
+        ),
+        (reply, TranscriptOutput::InProgress),
+    ] {
+        let mut batch = new_replies.clone();
+        batch.push(reply);
+
+        let mut coordinator = new_coordinator()?;
+        let outputs: Vec<_> = batch
+            .iter()
+            .copied()
+            .map(|(identifier, signature_share, signing_commitments)| {
+                TranscriptOutput::from(&coordinator.receive(
+                    identifier,
+                    signature_share,
+                    signing_commitments,
+                ))
+            })
+            .collect();
+        let batch_outputs: Vec<_> = new_coordinator()?
+            .receive_batch(batch)
+            .iter()
+            .map(TranscriptOutput::from)
+            .collect();
+
+        assert_eq!(outputs.last(), Some(&expected));
+        assert_eq!(outputs, batch_outputs);
+    }
+
+    Ok(())
+}
+
+/// Runs recorded ROAST algorithm with `min_signers`/`max_signers`
+/// multi-signature through single [`Coordinator::receive_many`] call and
+/// checks resulting events.
+pub fn test_receive_many<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    min_signers: u16,
+    max_signers: u16,
+    rng: &mut RNG,
+) -> Result<(), Error<C>>
+where
+    Identifier<C>: Sync,
+    SignatureShare<C>: Sync,
+    SigningPackage<C>: Sync,
+    VerifyingShare<C>: Sync,
+    VerifyingKey<C>: Sync,
+{
+    let (transcript, malicious_identifier) = record(min_signers, max_signers, rng)?;
+
+    let mut coordinator = Coordinator::<C>::new(
+        transcript.max_signers(),
+        transcript.min_signers(),
+        transcript.public_key_package().clone(),
+        transcript.message().to_vec(),
+    )?;
+
+    let mut replies = Vec::new();
+    for entry in transcript.entries() {
+        match entry.input.clone() {
+            TranscriptInput::Receive {
+                identifier,
+                signature_share,
+                signing_commitments,
+            } => replies.push((identifier, signature_share, signing_commitments)),
+            TranscriptInput::Preprocess {
+                identifier,
+                signing_commitments,
+            } => coordinator.preprocess(identifier, signing_commitments)?,
+        }
+    }
+
+    let events = coordinator.receive_many(replies).map_err(|err| err.error)?;
+
+    let mut session_ids = Vec::new();
+    let mut malicious_identifiers = Vec::new();
+    for event in &events {
+        match event {
+            CoordinatorEvent::SessionStarted { session_id, .. } => session_ids.push(*session_id),
+            CoordinatorEvent::SignerMarkedMalicious { identifier, .. } => {
+                malicious_identifiers.push(*identifier)
+            }
+            CoordinatorEvent::Finished { .. } => {}
+        }
+    }
+
+    assert_eq!(
+        session_ids,
+        (1..=coordinator.session_count()).collect::<Vec<_>>()
+    );
+    assert_eq!(malicious_identifiers, [malicious_identifier]);
+
+    match events.last() {
+        Some(CoordinatorEvent::Finished { signature }) => transcript
+            .public_key_package()
+            .verifying_key()
+            .verify(transcript.message(), signature)?,
+        event => panic!("expected finished event, got {event:?}"),
+    }
+
+    // Every signer replies twice, so that every second reply marks its signer
+    // as malicious until there are too many malicious signers.
+    let mut coordinator = Coordinator::<C>::new(
+        transcript.max_signers(),
+        transcript.min_signers(),
+        transcript.public_key_package().clone(),
+        transcript.message().to_vec(),
+    )?;
+    let replies = transcript
+        .entries()
+        .iter()
+        .filter_map(|entry| match entry.input {
+            TranscriptInput::Preprocess {
+                identifier,
+                ref signing_commitments,
+            } => Some((identifier, None, *signing_commitments.first()?)),
+            TranscriptInput::Receive { .. } => None,
+        })
+        .flat_map(|reply| [reply, reply])
+        .collect();
+
+    let err = coordinator.receive_many(replies).unwrap_err();
+    assert_eq!(err.error, Error::TooManyMaliciousSigners);
+    assert_eq!(
+        err.events
+            .iter()
+            .filter(|event| matches!(event, CoordinatorEvent::SignerMarkedMalicious { .. }))
+            .count(),
+        (max_signers - min_signers) as usize
+    );
+
+    Ok(())
+}
+
+/// Wraps replies and signing packages of recorded ROAST algorithm with
+/// `min_signers`/`max_signers` multi-signature into [`Envelope`]s.
+fn envelopes<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    min_signers: u16,
+    max_signers: u16,
+    rng: &mut RNG,
+) -> Result<(RunId, Vec<Envelope<C>>), Error<C>> {
+    let (transcript, _) = record(min_signers, max_signers, rng)?;
+
+    let mut run_id = [0; 32];
+    rng.fill_bytes(&mut run_id);
+    let run_id = RunId::new(run_id);
+
+    let mut session_id = 0;
+    let mut envelopes = Vec::new();
+    for entry in transcript.entries() {
+        if let TranscriptInput::Receive {
+            identifier,
+            signature_share,
+            signing_commitments,
+        } = entry.input
+        {
+            envelopes.push(Envelope::new(
+                run_id,
+                session_id,
+                Payload::Reply {
+                    identifier,
+                    signature_share,
+                    signing_commitments,
+                },
+            ));
+        }
+        if let TranscriptOutput::Started {
+            signing_package, ..
+        } = &entry.output
+        {
+            session_id += 1;
+            envelopes.push(Envelope::new(
+                run_id,
+                session_id,
+                Payload::SigningPackage(signing_package.clone()),
+            ));
+        }
+    }
+
+    Ok((run_id, envelopes))
+}
+
+/// Wraps replies and signing packages of ROAST algorithm with
+/// `min_signers`/`max_signers` multi-signature into [`Envelope`]s and checks
+/// that they round-trip and that mismatches are rejected.
+pub fn test_envelope<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    min_signers: u16,
+    max_signers: u16,
+    rng: &mut RNG,
+) -> Result<(), Error<C>> {
+    let (run_id, envelopes) = envelopes(min_signers, max_signers, rng)?;
+
+    let other_run_id = RunId::new([0xff; 32]);
+    for envelope in &envelopes {
+        envelope.verify(run_id, envelope.session_id())?;
+        assert_eq!(
+            envelope.verify(other_run_id, envelope.session_id()),
+            Err(Error::RunIdMismatch)
+        );
+        assert_eq!(
+            envelope.verify(run_id, envelope.session_id() + 1),
+            Err(Error::SessionIdMismatch(envelope.session_id()))
+        );
+
+        #[cfg(feature = "serialization")]
+        {
+            let serialization = envelope.serialize()?;
+            assert_eq!(&Envelope::deserialize(&serialization)?, envelope);
+
+            let mut other_version = serialization.clone();
+            other_version[..2].copy_from_slice(&(crate::PROTOCOL_VERSION + 1).to_be_bytes());
+            assert_eq!(
+                Envelope::<C>::deserialize(&other_version),
+                Err(Error::UnsupportedProtocolVersion(
+                    crate::PROTOCOL_VERSION + 1
+                ))
+            );
+
+            let mut other_ciphersuite = serialization.clone();
+            other_ciphersuite[6] ^= 0xff;
+            assert_eq!(
+                Envelope::<C>::deserialize(&other_ciphersuite),
+                Err(Error::CiphersuiteMismatch)
+            );
+
+            let mut trailing = serialization.clone();
+            trailing.push(0);
+            assert_eq!(
+                Envelope::<C>::deserialize(&trailing),
+                Err(Error::MalformedMessage)
+            );
+            assert_eq!(
+                Envelope::<C>::deserialize(&serialization[..serialization.len() - 1]),
+                Err(Error::MalformedMessage)
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Wraps replies and signing packages of ROAST algorithm with
+/// `min_signers`/`max_signers` multi-signature into [`Envelope`]s and checks
+/// that they round-trip through compact encoding.
+pub fn test_compact<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    min_signers: u16,
+    max_signers: u16,
+    rng: &mut RNG,
+) -> Result<(), Error<C>> {
+    let (_, envelopes) = envelopes(min_signers, max_signers, rng)?;
+
+    for envelope in &envelopes {
+        let len = envelope.compact_len()?;
+        let encoding = envelope.to_compact()?;
+        assert_eq!(encoding.len(), len);
+
+        let mut buf = vec![0; len];
+        assert_eq!(envelope.encode_compact(&mut buf)?, len);
+        assert_eq!(buf, encoding);
+        assert_eq!(
+            envelope.encode_compact(&mut buf[..len - 1]),
+            Err(Error::BufferTooSmall)
+        );
+
+        assert_eq!(&Envelope::decode_compact(&encoding)?, envelope);
+
+        let mut other_version = encoding.clone();
+        other_version[0] += 1;
+        assert_eq!(
+            Envelope::<C>::decode_compact(&other_version),
+            Err(Error::UnsupportedProtocolVersion(
+                crate::PROTOCOL_VERSION + 1
+            ))
+        );
+
+        let mut other_ciphersuite = encoding.clone();
+        other_ciphersuite[2] ^= 0xff;
+        assert_eq!(
+            Envelope::<C>::decode_compact(&other_ciphersuite),
+            Err(Error::CiphersuiteMismatch)
+        );
+
+        let mut trailing = encoding.clone();
+        trailing.push(0);
+        assert_eq!(
+            Envelope::<C>::decode_compact(&trailing),
+            Err(Error::MalformedMessage)
+        );
+        assert_eq!(
+            Envelope::<C>::decode_compact(&encoding[..len - 1]),
+            Err(Error::MalformedMessage)
+        );
+    }
+
+    Ok(())
+}
+
+/// Wraps replies and signing packages of ROAST algorithm with
+/// `min_signers`/`max_signers` multi-signature into [`Envelope`]s and checks
+/// that they round-trip through protobuf encoding.
+#[cfg(feature = "protobuf")]
+pub fn test_protobuf<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    min_signers: u16,
+    max_signers: u16,
+    rng: &mut RNG,
+) -> Result<(), Error<C>> {
+    use crate::protobuf;
+    use prost::Message;
+
+    check_protobuf_schema();
+
+    let (_, envelopes) = envelopes(min_signers, max_signers, rng)?;
+
+    for envelope in &envelopes {
+        let encoding = envelope.encode_protobuf()?;
+        assert_eq!(&Envelope::decode_protobuf(&encoding)?, envelope);
+
+        let message = protobuf::Envelope::try_from(envelope)?;
+        assert_eq!(Envelope::<C>::try_from(message.clone())?, *envelope);
+
+        let mut other_version = message.clone();
+        other_version.version += 1;
+        assert_eq!(
+            Envelope::<C>::decode_protobuf(&other_version.encode_to_vec()),
+            Err(Error::UnsupportedProtocolVersion(
+                crate::PROTOCOL_VERSION + 1
+            ))
+        );
+
+        let mut unknown_version = message.clone();
+        unknown_version.version = u32::MAX;
+        assert_eq!(
+            Envelope::<C>::decode_protobuf(&unknown_version.encode_to_vec()),
+            Err(Error::MalformedMessage)
+        );
+
+        if let Some(protobuf::envelope::Payload::SigningPackage(signing_package)) = &message.payload
+        {
+            let mut duplicate_signer = signing_package.clone();
+            duplicate_signer
+                .commitments
+                .push(duplicate_signer.commitments[0].clone());
+            assert_eq!(
+                SigningPackage::<C>::try_from(duplicate_signer),
+                Err(Error::MalformedMessage)
+            );
+        }
+
+        let mut other_ciphersuite = message.clone();
+        other_ciphersuite.ciphersuite.push('!');
+        assert_eq!(
+            Envelope::<C>::decode_protobuf(&other_ciphersuite.encode_to_vec()),
+            Err(Error::CiphersuiteMismatch)
+        );
+
+        let mut missing_payload = message;
+        missing_payload.payload = None;
+        assert_eq!(
+            Envelope::<C>::decode_protobuf(&missing_payload.encode_to_vec()),
+            Err(Error::MalformedMessage)
+        );
+    }
+
+    Ok(())
+}
+
+/// Checks that every field of [`protobuf::SCHEMA`](crate::protobuf::SCHEMA) is
+/// encoded with the same tag and wire type by the corresponding message of
+/// [`protobuf`](crate::protobuf) module and vice versa.
+#[cfg(feature = "protobuf")]
+fn check_protobuf_schema() {
+    use crate::protobuf::{
+        envelope::Payload, Envelope, Reply, SignerCommitments, SigningCommitments, SigningPackage,
+    };
+    use prost::Message;
+
+    let mut schema_fields = BTreeSet::new();
+    let mut scopes = Vec::new();
+    for line in crate::protobuf::SCHEMA.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if let Some(scope) = line.strip_suffix('{') {
+            scopes.push(scope.trim());
+        } else if line == "}" {
+            scopes.pop();
+        } else if let Some((declaration, tag)) =
+            line.strip_suffix(';').and_then(|line| line.split_once('='))
+        {
+            let Some(message) = scopes
+                .iter()
+                .rev()
+                .find_map(|scope| scope.strip_prefix("message "))
+            else {
+                continue;
+            };
+            let mut tokens = declaration.split_whitespace().rev();
+            let name = tokens.next().unwrap();
+            let wire_type = match tokens.next().unwrap() {
+                "uint32" => 0,
+                _ => 2,
+            };
+            schema_fields.insert((message, name, tag.trim().parse::<u8>().unwrap(), wire_type));
+        }
+    }
+
+    let signing_commitments = || Some(SigningCommitments::default());
+    let encodings = [
+        (
+            "SigningCommitments",
+            "hiding",
+            SigningCommitments {
+                hiding: vec![1],
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        ),
+        (
+            "SigningCommitments",
+            "binding",
+            SigningCommitments {
+                binding: vec![1],
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        ),
+        (
+            "Reply",
+            "identifier",
+            Reply {
+                identifier: vec![1],
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        ),
+        (
+            "Reply",
+            "signature_share",
+            Reply {
+                signature_share: Some(vec![1]),
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        ),
+        (
+            "Reply",
+            "signing_commitments",
+            Reply {
+                signing_commitments: signing_commitments(),
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        ),
+        (
+            "SignerCommitments",
+            "identifier",
+            SignerCommitments {
+                identifier: vec![1],
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        ),
+        (
+            "SignerCommitments",
+            "signing_commitments",
+            SignerCommitments {
+                signing_commitments: signing_commitments(),
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        ),
+        (
+            "SigningPackage",
+            "commitments",
+            SigningPackage {
+                commitments: vec![SignerCommitments::default()],
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        ),
+        (
+            "SigningPackage",
+            "message",
+            SigningPackage {
+                message: vec![1],
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        ),
+        (
+            "Envelope",
+            "version",
+            Envelope {
+                version: 1,
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        ),
+        (
+            "Envelope",
+            "ciphersuite",
+            Envelope {
+                ciphersuite: "ciphersuite".into(),
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        ),
+        (
+            "Envelope",
+            "run_id",
+            Envelope {
+                run_id: vec![1],
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        ),
+        (
+            "Envelope",
+            "session_id",
+            Envelope {
+                session_id: 1,
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        ),
+        (
+            "Envelope",
+            "reply",
+            Envelope {
+                payload: Some(Payload::Reply(Reply::default())),
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        ),
+        (
+            "Envelope",
+            "signing_package",
+            Envelope {
+                payload: Some(Payload::SigningPackage(SigningPackage::default())),
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        ),
+    ];
+    let fields: BTreeSet<_> = encodings
+        .iter()
+        .map(|(message, name, encoding)| (*message, *name, encoding[0] >> 3, encoding[0] & 7))
+        .collect();
+
+    assert_eq!(fields, schema_fields);
+}
+
+/// Runs ROAST algorithm with `min_signers`/`max_signers` multi-signature
+/// through [`Envelope`]s and checks that messages of the run are rejected by
+/// coordinator and signers of other runs with the same key.
+pub fn test_run_id<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    min_signers: u16,
+    max_signers: u16,
+    rng: &mut RNG,
+) -> Result<(), Error<C>> {
+    let (secret_shares, public_key_package) =
+        keys::generate_with_dealer(max_signers, min_signers, IdentifierList::Default, rng)?;
+
+    let message = b"message to sign".to_vec();
+    let other_message = b"other message to sign".to_vec();
+
+    let mut run_id = [0; 32];
+    rng.fill_bytes(&mut run_id);
+    let run_id = RunId::new(run_id);
+
+    let mut coordinator = Coordinator::new(
+        max_signers,
+        min_signers,
+        public_key_package.clone(),
+        message.clone(),
+    )?;
+    // Coordinator that signs other message with the same key.
+    let mut other_coordinator = Coordinator::new(
+        max_signers,
+        min_signers,
+        public_key_package.clone(),
+        other_message.clone(),
+    )?;
+    // Coordinator that signs the same message with the same key.
+    let mut same_message_coordinator = Coordinator::new(
+        max_signers,
+        min_signers,
+        public_key_package.clone(),
+        message.clone(),
+    )?
+    .with_run_id(run_id);
+    assert_ne!(coordinator.run_id(), other_coordinator.run_id());
+    assert_ne!(coordinator.run_id(), same_message_coordinator.run_id());
+
+    let mut signers: BTreeMap<_, _> = BTreeMap::new();
+    let mut other_signers: BTreeMap<_, _> = BTreeMap::new();
+    for (identifier, secret_share) in secret_shares {
+        let key_package = KeyPackage::try_from(secret_share)?;
+        let signer = Signer::new(key_package.clone(), message.clone(), rng)?;
+        assert_eq!(signer.run_id(), coordinator.run_id());
+        signers.insert(identifier, signer);
+        let other_signer = Signer::new(key_package, other_message.clone(), rng)?;
+        assert_eq!(other_signer.run_id(), other_coordinator.run_id());
+        other_signers.insert(identifier, other_signer);
+    }
+
+    let first_envelopes: BTreeMap<_, _> = signers
+        .iter()
+        .map(|(identifier, signer)| (*identifier, signer.envelope()))
+        .collect();
+    let mut pending: Vec<_> = first_envelopes.values().cloned().collect();
+
+    let signature = loop {
+        let envelope = pending.remove(0);
+
+        assert!(matches!(
+            other_coordinator.receive_envelope(envelope.clone()),
+            Err(Error::RunIdMismatch)
+        ));
+        assert!(matches!(
+            same_message_coordinator.receive_envelope(envelope.clone()),
+            Err(Error::RunIdMismatch)
+        ));
+
+        let session_id = envelope.session_id();
+        match coordinator.receive_envelope(envelope.clone())? {
+            SessionStatus::InProgress => {
+                // Replayed reply of responsive signer is rejected without
+                // marking it as malicious.
+                let result = coordinator.receive_envelope(envelope);
+                if session_id == 0 {
+                    assert!(matches!(result, Err(Error::ReplayedEnvelope)));
+                } else {
+                    assert!(matches!(result, Err(Error::SessionIdMismatch(_))));
+                }
+            }
+            SessionStatus::Started { signers: ids, .. } => {
+                let envelope = coordinator.envelope(coordinator.session_count()).unwrap();
+                for identifier in ids {
+                    // Replayed first reply does not belong to the current
+                    // session of the signer and is rejected without marking
+                    // it as malicious.
+                    assert!(matches!(
+                        coordinator.receive_envelope(first_envelopes[&identifier].clone()),
+                        Err(Error::SessionIdMismatch(0))
+                    ));
+
+                    let other_signer = other_signers.get_mut(&identifier).unwrap();
+                    assert!(matches!(
+                        other_signer.receive_envelope(&envelope, rng),
+                        Err(Error::RunIdMismatch)
+                    ));
+
+                    let signer = signers.get_mut(&identifier).unwrap();
+                    pending.push(signer.receive_envelope(&envelope, rng)?);
+                }
+            }
+            SessionStatus::Finished { signature } => break signature,
+        }
+    };
+
+    public_key_package
+        .verifying_key()
+        .verify(&message, &signature)?;
+
+    assert!(coordinator.malicious_signers().is_empty());
+    assert!(other_coordinator.malicious_signers().is_empty());
+    assert!(same_message_coordinator.malicious_signers().is_empty());
+
+    Ok(())
+}
+
+/// Runs ROAST algorithm with `min_signers`/`max_signers` multi-signature and
+/// one malicious signer through [`SignedEnvelope`]s and checks that an
+/// attacker cannot get honest signers marked as malicious by impersonating
+/// them.
+pub fn test_authentication<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    min_signers: u16,
+    max_signers: u16,
+    rng: &mut RNG,
+) -> Result<(), Error<C>>
+where
+    Identifier<C>: Sync,
+    SignatureShare<C>: Sync,
+    SigningPackage<C>: Sync,
+    VerifyingShare<C>: Sync,
+    VerifyingKey<C>: Sync,
+{
+    assert!(min_signers < max_signers);
+
+    let (secret_shares, public_key_package) =
+        keys::generate_with_dealer(max_signers, min_signers, IdentifierList::Default, rng)?;
+
+    let message = b"message to sign".to_vec();
+    let mut signers: BTreeMap<_, _> = BTreeMap::new();
+    let mut identity_keys: BTreeMap<_, _> = BTreeMap::new();
+    for (identifier, secret_share) in secret_shares {
+        let key_package = KeyPackage::try_from(secret_share)?;
+        let signer = Signer::new(key_package, message.clone(), rng)?;
+        signers.insert(identifier, (signer, SigningKey::<C>::new(rng)));
+    }
+    for (identifier, (_, signing_key)) in &signers {
+        identity_keys.insert(*identifier, VerifyingKey::from(signing_key));
+    }
+
+    let mut coordinator = Coordinator::new(
+        max_signers,
+        min_signers,
+        public_key_package.clone(),
+        message.clone(),
+    )?
+    .with_identity_keys(identity_keys.clone());
+    let mut liveness_coordinator = LivenessCoordinator::new(
+        Coordinator::new(
+            max_signers,
+            min_signers,
+            public_key_package.clone(),
+            message.clone(),
+        )?
+        .with_identity_keys(identity_keys),
+        Duration::from_secs(10),
+        TimeoutPolicy::Report,
+    );
+
+    let malicious_identifier = *signers.keys().next().unwrap();
+    let attacker_key = SigningKey::<C>::new(rng);
+    let zero = <<C::Group as Group>::Field as Field>::zero();
+    let serialization = <<C::Group as Group>::Field as Field>::serialize(&zero);
+    let garbage_share = SignatureShare::<C>::deserialize(serialization.as_ref())?;
+
+    let mut pending = Vec::new();
+    for (signer, signing_key) in signers.values() {
+        let signed_envelope = SignedEnvelope::sign(signer.envelope(), signing_key, rng)?;
+        assert_eq!(
+            SignedEnvelope::decode_compact(&signed_envelope.to_compact()?)?,
+            signed_envelope
+        );
+        assert!(matches!(
+            coordinator.receive_envelope(signed_envelope.envelope().clone()),
+            Err(Error::UnsignedEnvelope)
+        ));
+
+        // Replies can be received only in signed envelopes.
+        let Payload::Reply {
+            identifier,
+            signature_share,
+            signing_commitments,
+        } = *signed_envelope.envelope().payload()
+        else {
+            unreachable!()
+        };
+        assert!(matches!(
+            coordinator.receive(identifier, signature_share, signing_commitments),
+            Err(Error::UnsignedEnvelope)
+        ));
+        assert!(matches!(
+            coordinator.preprocess(identifier, vec![signing_commitments]),
+            Err(Error::UnsignedEnvelope)
+        ));
+        assert!(matches!(
+            coordinator
+                .receive_many(vec![(identifier, signature_share, signing_commitments)])
+                .map_err(|err| err.error),
+            Err(Error::UnsignedEnvelope)
+        ));
+        assert!(matches!(
+            liveness_coordinator.receive(
+                Duration::ZERO,
+                identifier,
+                signature_share,
+                signing_commitments
+            ),
+            Err(Error::UnsignedEnvelope)
+        ));
+        liveness_coordinator.receive_signed_envelope(Duration::ZERO, signed_envelope.clone())?;
+
+        pending.push(signed_envelope);
+    }
+    assert!(coordinator.responsive_signers().is_empty());
+
+    let signature = loop {
+        let signed_envelope = pending.remove(0);
+
+        // Attacker replays the reply with unsolicited signature share on
+        // behalf of its sender.
+        let forged_envelope = Envelope::new(
+            signed_envelope.envelope().run_id(),
+            signed_envelope.envelope().session_id(),
+            match signed_envelope.envelope().payload().clone() {
+                Payload::Reply {
+                    identifier,
+                    signing_commitments,
+                    ..
+                } => Payload::Reply {
+                    identifier,
+                    signature_share: Some(garbage_share),
+                    signing_commitments,
+                },
+                Payload::SigningPackage(_) => unreachable!(),
+            },
+        );
+        let forged = SignedEnvelope::sign(forged_envelope.clone(), &attacker_key, rng)?;
+        assert!(matches!(
+            coordinator.receive_signed_envelope(forged),
+            Err(Error::InvalidEnvelopeSignature)
+        ));
+        let forged = SignedEnvelope::new(forged_envelope, *signed_envelope.signature());
+        assert!(matches!(
+            coordinator.receive_signed_envelope(forged),
+            Err(Error::InvalidEnvelopeSignature)
+        ));
+
+        let session_id = signed_envelope.envelope().session_id();
+        match coordinator.receive_signed_envelope(signed_envelope.clone()) {
+            Ok(SessionStatus::InProgress) if session_id == 0 => {
+                // Replayed first reply of responsive signer is rejected
+                // without marking it as malicious.
+                assert!(matches!(
+                    coordinator.receive_signed_envelope(signed_envelope),
+                    Err(Error::ReplayedEnvelope)
+                ));
+            }
+            Ok(SessionStatus::InProgress) => {}
+            Ok(SessionStatus::Started { signers: ids, .. }) => {
+                let envelope = coordinator.envelope(coordinator.session_count()).unwrap();
+                for identifier in ids {
+                    let (signer, signing_key) = signers.get_mut(&identifier).unwrap();
+                    let mut reply = signer.receive_envelope(&envelope, rng)?;
+                    if identifier == malicious_identifier {
+                        let Payload::Reply {
+                            signing_commitments,
+                            ..
+                        } = *reply.payload()
+                        else {
+                            unreachable!()
+                        };
+                        reply = Envelope::new(
+                            reply.run_id(),
+                            reply.session_id(),
+                            Payload::Reply {
+                                identifier,
+                                signature_share: Some(garbage_share),
+                                signing_commitments,
+                            },
+                        );
+                    }
+                    pending.push(SignedEnvelope::sign(reply, signing_key, rng)?);
+                }
+            }
+            Ok(SessionStatus::Finished { signature }) => break signature,
+            Err(Error::MaliciousSigner(_)) => {}
+            Err(err) => return Err(err),
+        }
+    };
+
+    public_key_package
+        .verifying_key()
+        .verify(&message, &signature)?;
+
+    assert_eq!(
+        coordinator
+            .malicious_signers()
+            .keys()
+            .copied()
+            .collect::<Vec<_>>(),
+        [malicious_identifier]
+    );
+
+    Ok(())
+}
+
+/// Runs ROAST algorithm with `min_signers`/`max_signers` multi-signature and
+/// several coordinators, one of which never sends signing packages, and
+/// checks that signing packages of one coordinator cannot be used in the run
+/// of another one.
+pub fn test_multi_coordinator<C: Ciphersuite, RNG: RngCore + CryptoRng>(
+    min_signers: u16,
+    max_signers: u16,
+    rng: &mut RNG,
+) -> Result<(), Error<C>> {
+    let (secret_shares, public_key_package) =
+        keys::generate_with_dealer(max_signers, min_signers, IdentifierList::Default, rng)?;
+
+    let message = b"message to sign".to_vec();
+    let run_ids: Vec<_> = (0..3)
+        .map(|_| {
+            let mut run_id = [0; 32];
+            rng.fill_bytes(&mut run_id);
+            RunId::new(run_id)
+        })
+        .collect();
+    let faulty_run_id = run_ids[0];
+
+    assert!(matches!(
+        MultiCoordinator::new(
+            max_signers,
+            min_signers,
+            public_key_package.clone(),
+            message.clone(),
+            [run_ids[1], run_ids[1]],
+        ),
+        Err(Error::DuplicateRunId)
+    ));
+
+    let key_package = KeyPackage::try_from(secret_shares.values().next().unwrap().clone())?;
+    assert!(matches!(
+        MultiSigner::with_nonce_stores(
+            key_package,
+            message.clone(),
+            [run_ids[1], run_ids[1]].map(|run_id| (run_id, MemoryNonceStore::new())),
+            rng,
+        ),
+        Err(Error::DuplicateRunId)
+    ));
+
+    let mut signers: BTreeMap<_, _> = BTreeMap::new();
+    let mut identity_keys: BTreeMap<_, _> = BTreeMap::new();
+    let mut pending = Vec::new();
+    for (identifier, secret_share) in secret_shares {
+        let key_package = KeyPackage::try_from(secret_share)?;
+        let signer = MultiSigner::new(key_package, message.clone(), run_ids.iter().copied(), rng)?;
+        let signing_key = SigningKey::<C>::new(rng);
+        for envelope in signer.envelopes() {
+            pending.push(SignedEnvelope::sign(envelope, &signing_key, rng)?);
+        }
+        identity_keys.insert(identifier, VerifyingKey::from(&signing_key));
+        signers.insert(identifier, (signer, signing_key));
+    }
+    pending.shuffle(rng);
+
+    /// Records the number of started sessions of one run.
+    #[derive(Debug, Default)]
+    struct SessionCount(crate::SessionId);
+
+    impl Metrics for SessionCount {
+        fn session_started(&mut self, session_id: crate::SessionId) {
+            self.0 = session_id;
+        }
+    }
+
+    let mut coordinator = MultiCoordinator::with_metrics(
+        max_signers,
+        min_signers,
+        public_key_package.clone(),
+        message.clone(),
+        run_ids
+            .iter()
+            .map(|run_id| (*run_id, SessionCount::default())),
+    )?
+    .with_identity_keys(identity_keys);
+
+    // Every coordinator accepts only signed replies.
+    for signed_envelope in &pending {
+        assert!(matches!(
+            coordinator.receive_envelope(signed_envelope.envelope().clone()),
+            Err(Error::UnsignedEnvelope)
+        ));
+    }
+
+    let signature = loop {
+        let signed_envelope = pending.remove(0);
+        let run_id = signed_envelope.envelope().run_id();
+
+        match coordinator.receive_signed_envelope(signed_envelope)? {
+            SessionStatus::InProgress => {}
+            SessionStatus::Started { signers: ids, .. } => {
+                // Faulty coordinator never sends signing packages.
+                if run_id == faulty_run_id {
+                    continue;
+                }
+
+                let session_id = coordinator.coordinator(run_id).unwrap().session_count();
+                let envelope = coordinator.envelope(run_id, session_id).unwrap();
+                for identifier in ids {
+                    let (signer, signing_key) = signers.get_mut(&identifier).unwrap();
+
+                    // Signing package of this run replayed in another run
+                    // refers to nonces of this run and is rejected without
+                    // using any nonces.
+                    for other_run_id in run_ids.iter().filter(|other| **other != run_id) {
+                        let replayed = Envelope::new(
+                            *other_run_id,
+                            envelope.session_id(),
+                            envelope.payload().clone(),
+                        );
+                        assert!(matches!(
+                            signer.receive_envelope(&replayed, rng),
+                            Err(Error::MaliciousCoordinator(
+                                MaliciousCoordinatorError::InvalidSigningCommitments
+                            ))
+                        ));
+                    }
+
+                    let reply = signer.receive_envelope(&envelope, rng)?;
+                    pending.push(SignedEnvelope::sign(reply, signing_key, rng)?);
+                }
+            }
+            SessionStatus::Finished { signature } => break signature,
+        }
+    };
+
+    public_key_package
+        .verifying_key()
+        .verify(&message, &signature)?;
+    assert_eq!(coordinator.signature(), Some(signature));
+
+    // The first valid signature is kept.
+    for signed_envelope in pending {
+        assert!(matches!(
+            coordinator.receive_signed_envelope(signed_envelope)?,
+            SessionStatus::Finished { signature: kept } if kept == signature
+        ));
+    }
+
+    for run_id in run_ids {
+        let coordinator = coordinator.coordinator(run_id).unwrap();
+        assert!(coordinator.malicious_signers().is_empty());
+        assert_eq!(coordinator.metrics().0, coordinator.session_count());
+    }
+
+    // Signer of the faulty run can still preprocess nonces for it.
+    for (signer, _) in signers.values_mut() {
+        let signing_commitments = signer
+            .signer_mut(faulty_run_id)
+            .unwrap()
+            .preprocess(1, rng)?;
+        assert!(signer
+            .signer(faulty_run_id)
+            .unwrap()
+            .nonce_store()
+            .contains(&signing_commitments[0]));
+    }
+
     Ok(())
 }
@@ -0,0 +1,263 @@
+//! Adversary strategies.
+
+use crate::{
+    frost::{
+        keys::{self, IdentifierList, KeyPackage},
+        Ciphersuite,
+    },
+    Coordinator, Error, SessionStatus, Signer,
+};
+use alloc::{collections::BTreeMap, vec::Vec};
+use frost_core::{
+    round1::SigningCommitments, round2::SignatureShare, Field, Group, SigningPackage,
+};
+use rand::{seq::SliceRandom, CryptoRng, RngCore};
+
+/// Represents reply of signer to the coordinator.
+pub type Reply<C> = (Option<SignatureShare<C>>, SigningCommitments<C>);
+
+/// Represents behaviour of malicious signer.
+///
+/// Default implementations behave like honest signer.
+pub trait Adversary<C: Ciphersuite> {
+    /// Returns replies that the signer sends to the coordinator when it joins
+    /// ROAST.
+    fn start<RNG: RngCore + CryptoRng>(
+        &mut self,
+        signer: &mut Signer<C>,
+        _rng: &mut RNG,
+    ) -> Vec<Reply<C>> {
+        vec![(None, signer.signing_commitments())]
+    }
+
+    /// Returns replies that the signer sends to the coordinator when it
+    /// receives [`SigningPackage`].
+    fn receive<RNG: RngCore + CryptoRng>(
+        &mut self,
+        signer: &mut Signer<C>,
+        signing_package: &SigningPackage<C>,
+        rng: &mut RNG,
+    ) -> Vec<Reply<C>> {
+        let signature_share = signer
+            .receive(signing_package, rng)
+            .expect("signing package is valid");
+        vec![(Some(signature_share), signer.signing_commitments())]
+    }
+}
+
+/// Represents honest signer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Honest;
+
+impl<C: Ciphersuite> Adversary<C> for Honest {}
+
+/// Represents signer that never replies to [`SigningPackage`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WithholdShares;
+
+impl<C: Ciphersuite> Adversary<C> for WithholdShares {
+    fn receive<RNG: RngCore + CryptoRng>(
+        &mut self,
+        _signer: &mut Signer<C>,
+        _signing_package: &SigningPackage<C>,
+        _rng: &mut RNG,
+    ) -> Vec<Reply<C>> {
+        Vec::new()
+    }
+}
+
+/// Represents signer that replies to [`SigningPackage`] with random
+/// [`SignatureShare`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GarbageShares;
+
+impl<C: Ciphersuite> Adversary<C> for GarbageShares {
+    fn receive<RNG: RngCore + CryptoRng>(
+        &mut self,
+        signer: &mut Signer<C>,
+        _signing_package: &SigningPackage<C>,
+        rng: &mut RNG,
+    ) -> Vec<Reply<C>> {
+        let scalar = <<C::Group as Group>::Field as Field>::random(rng);
+        let serialization = <<C::Group as Group>::Field as Field>::serialize(&scalar);
+        let signature_share = SignatureShare::<C>::deserialize(serialization.as_ref())
+            .expect("scalar is serialized correctly");
+        signer
+            .regenerate_signing_nonces(rng)
+            .expect("nonce store is in memory");
+        vec![(Some(signature_share), signer.signing_commitments())]
+    }
+}
+
+/// Represents signer that replies to [`SigningPackage`] with valid
+/// [`SignatureShare`], but with [`SigningCommitments`] it has already sent.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReplayCommitments;
+
+impl<C: Ciphersuite> Adversary<C> for ReplayCommitments {
+    fn receive<RNG: RngCore + CryptoRng>(
+        &mut self,
+        signer: &mut Signer<C>,
+        signing_package: &SigningPackage<C>,
+        rng: &mut RNG,
+    ) -> Vec<Reply<C>> {
+        let signing_commitments = signer
+            .verify_signing_package(signing_package)
+            .expect("signing package is valid");
+        let signature_share = signer
+            .receive(signing_package, rng)
+            .expect("signing package is valid");
+        vec![(Some(signature_share), signing_commitments)]
+    }
+}
+
+/// Represents signer that never replies to the coordinator.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Crash;
+
+impl<C: Ciphersuite> Adversary<C> for Crash {
+    fn start<RNG: RngCore + CryptoRng>(
+        &mut self,
+        _signer: &mut Signer<C>,
+        _rng: &mut RNG,
+    ) -> Vec<Reply<C>> {
+        Vec::new()
+    }
+}
+
+/// Represents signer that replies to the coordinator while it waits for a new
+/// session.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnsolicitedReply;
+
+impl<C: Ciphersuite> Adversary<C> for UnsolicitedReply {
+    fn start<RNG: RngCore + CryptoRng>(
+        &mut self,
+        signer: &mut Signer<C>,
+        rng: &mut RNG,
+    ) -> Vec<Reply<C>> {
+        let mut replies = vec![(None, signer.signing_commitments())];
+        replies.extend(
+            signer
+                .preprocess(1, rng)
+                .expect("nonce store is in memory")
+                .into_iter()
+                .map(|signing_commitments| (None, signing_commitments)),
+        );
+        replies
+    }
+}
+
+/// Represents signer that sends two conflicting replies to [`SigningPackage`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Equivocate;
+
+impl<C: Ciphersuite> Adversary<C> for Equivocate {
+    fn receive<RNG: RngCore + CryptoRng>(
+        &mut self,
+        signer: &mut Signer<C>,
+        signing_package: &SigningPackage<C>,
+        rng: &mut RNG,
+    ) -> Vec<Reply<C>> {
+        let signature_share = signer
+            .receive(signing_package, rng)
+            .expect("signing package is valid");
+        let mut replies = vec![(Some(signature_share), signer.signing_commitments())];
+        replies.extend(
+            signer
+                .preprocess(1, rng)
+                .expect("nonce store is in memory")
+                .into_iter()
+                .map(|signing_commitments| (Some(signature_share), signing_commitments)),
+        );
+        replies
+    }
+}
+
+/// Runs ROAST algorithm with `min_signers`/`max_signers` multi-signature and
+/// `malicious_signers` that follow `adversary` strategy.
+///
+/// Replies are delivered to the coordinator in random order. Checks that the
+/// final signature is produced and that at most `malicious_signers + 1`
+/// sessions are started.
+pub fn test_adversary<C: Ciphersuite, A: Adversary<C> + Clone, RNG: RngCore + CryptoRng>(
+    min_signers: u16,
+    max_signers: u16,
+    malicious_signers: u16,
+    adversary: A,
+    rng: &mut RNG,
+) -> Result<(), Error<C>> {
+    assert!(malicious_signers <= max_signers - min_signers);
+
+    let (secret_shares, public_key_package) =
+        keys::generate_with_dealer(max_signers, min_signers, IdentifierList::Default, rng)?;
+
+    let message = b"message to sign".to_vec();
+    let mut coordinator = Coordinator::new(
+        max_signers,
+        min_signers,
+        public_key_package,
+        message.clone(),
+    )?;
+
+    let mut malicious_mask = vec![true; malicious_signers as usize];
+    malicious_mask.resize(max_signers as usize, false);
+    malicious_mask.shuffle(rng);
+
+    let mut signers: BTreeMap<_, _> = BTreeMap::new();
+    let mut adversaries: BTreeMap<_, _> = BTreeMap::new();
+    let mut replies = Vec::new();
+
+    for ((identifier, secret_share), is_malicious) in secret_shares.into_iter().zip(malicious_mask)
+    {
+        let key_package = KeyPackage::try_from(secret_share)?;
+        let mut signer = Signer::new(key_package, message.clone(), rng)?;
+
+        let signer_replies = if is_malicious {
+            let mut adversary = adversary.clone();
+            let signer_replies = adversary.start(&mut signer, rng);
+            adversaries.insert(identifier, adversary);
+            signer_replies
+        } else {
+            Honest.start(&mut signer, rng)
+        };
+
+        replies.extend(signer_replies.into_iter().map(|reply| (identifier, reply)));
+        signers.insert(identifier, signer);
+    }
+
+    let mut session_counter = 0;
+
+    loop {
+        assert!(!replies.is_empty(), "ROAST must not stall");
+
+        let index = rng.next_u32() as usize % replies.len();
+        let (identifier, (signature_share, signing_commitments)) = replies.swap_remove(index);
+
+        match coordinator.receive(identifier, signature_share, signing_commitments) {
+            Ok(SessionStatus::InProgress) => {}
+            Ok(SessionStatus::Started {
+                signers: session_signers,
+                signing_package,
+            }) => {
+                session_counter += 1;
+
+                for identifier in session_signers {
+                    let signer = signers.get_mut(&identifier).unwrap();
+                    let signer_replies = match adversaries.get_mut(&identifier) {
+                        Some(adversary) => adversary.receive(signer, &signing_package, rng),
+                        None => Honest.receive(signer, &signing_package, rng),
+                    };
+                    replies.extend(signer_replies.into_iter().map(|reply| (identifier, reply)));
+                }
+            }
+            Ok(SessionStatus::Finished { .. }) => break,
+            Err(Error::MaliciousSigner(_)) if adversaries.contains_key(&identifier) => {}
+            Err(err) => return Err(err),
+        }
+    }
+
+    assert!(session_counter <= malicious_signers + 1);
+
+    Ok(())
+}
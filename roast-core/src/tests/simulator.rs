@@ -0,0 +1,245 @@
+//! Deterministic network simulator.
+
+use super::adversary::{Adversary, Honest, Reply};
+use crate::{
+    frost::{
+        keys::{self, IdentifierList, KeyPackage},
+        Ciphersuite,
+    },
+    Coordinator, Error, SessionId, SessionStatus, Signer,
+};
+use alloc::{collections::BTreeMap, vec::Vec};
+use frost_core::{Identifier, SigningPackage};
+use rand::{seq::SliceRandom, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+/// Represents all possible orders in which the simulated network delivers
+/// messages.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum DeliveryOrder {
+    /// Delivers a random pending message.
+    Random,
+    /// Delays each message by a random number of steps up to `max_delay` and
+    /// delivers messages in order of their delivery time.
+    Delayed {
+        /// Maximum delay of a message in steps.
+        max_delay: u64,
+    },
+    /// Delivers messages from and to malicious signers first, and the newest
+    /// messages from and to honest signers after them.
+    Adversarial,
+}
+
+#[derive(Debug)]
+enum Payload<C: Ciphersuite> {
+    ToCoordinator(Reply<C>),
+    ToSigner(SigningPackage<C>),
+}
+
+#[derive(Debug)]
+struct Message<C: Ciphersuite> {
+    deliver_at: u64,
+    sequence: u64,
+    identifier: Identifier<C>,
+    payload: Payload<C>,
+}
+
+struct Network<C: Ciphersuite> {
+    delivery_order: DeliveryOrder,
+    now: u64,
+    sequence: u64,
+    messages: Vec<Message<C>>,
+}
+
+impl<C: Ciphersuite> Network<C> {
+    fn send(&mut self, identifier: Identifier<C>, payload: Payload<C>, rng: &mut ChaCha20Rng) {
+        let delay = match self.delivery_order {
+            DeliveryOrder::Delayed { max_delay } => rng.next_u64() % max_delay.saturating_add(1),
+            DeliveryOrder::Random | DeliveryOrder::Adversarial => 0,
+        };
+
+        self.messages.push(Message {
+            deliver_at: self.now.saturating_add(delay),
+            sequence: self.sequence,
+            identifier,
+            payload,
+        });
+        self.sequence += 1;
+    }
+
+    fn next<A>(
+        &mut self,
+        adversaries: &BTreeMap<Identifier<C>, A>,
+        rng: &mut ChaCha20Rng,
+    ) -> Option<Message<C>> {
+        let index = match self.delivery_order {
+            DeliveryOrder::Random => {
+                (!self.messages.is_empty()).then(|| rng.next_u32() as usize % self.messages.len())
+            }
+            DeliveryOrder::Delayed { .. } => self
+                .messages
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, message)| (message.deliver_at, message.sequence))
+                .map(|(index, _)| index),
+            DeliveryOrder::Adversarial => self
+                .messages
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, message)| {
+                    (
+                        adversaries.contains_key(&message.identifier),
+                        message.sequence,
+                    )
+                })
+                .map(|(index, _)| index),
+        }?;
+
+        let message = self.messages.swap_remove(index);
+        self.now = self.now.max(message.deliver_at) + 1;
+        Some(message)
+    }
+}
+
+/// Runs ROAST algorithm with `min_signers`/`max_signers` multi-signature and
+/// `malicious_signers` that follow `adversary` strategy over simulated network
+/// that delivers messages in `delivery_order`.
+///
+/// All randomness is derived from `seed`, so the same `seed` always produces
+/// the same run. Returns the number of started sessions.
+pub fn simulate<C: Ciphersuite, A: Adversary<C> + Clone>(
+    min_signers: u16,
+    max_signers: u16,
+    malicious_signers: u16,
+    adversary: A,
+    delivery_order: DeliveryOrder,
+    seed: u64,
+) -> Result<SessionId, Error<C>> {
+    assert!(malicious_signers <= max_signers - min_signers);
+
+    let mut rng = ChaCha20Rng::seed_from_u64(seed);
+
+    let (secret_shares, public_key_package) =
+        keys::generate_with_dealer(max_signers, min_signers, IdentifierList::Default, &mut rng)?;
+
+    let message = b"message to sign".to_vec();
+    let mut coordinator = Coordinator::new(
+        max_signers,
+        min_signers,
+        public_key_package.clone(),
+        message.clone(),
+    )?;
+
+    let mut malicious_mask = vec![true; malicious_signers as usize];
+    malicious_mask.resize(max_signers as usize, false);
+    malicious_mask.shuffle(&mut rng);
+
+    let mut network = Network {
+        delivery_order,
+        now: 0,
+        sequence: 0,
+        messages: Vec::new(),
+    };
+    let mut signers: BTreeMap<_, _> = BTreeMap::new();
+    let mut adversaries: BTreeMap<_, _> = BTreeMap::new();
+
+    for ((identifier, secret_share), is_malicious) in secret_shares.into_iter().zip(malicious_mask)
+    {
+        let key_package = KeyPackage::try_from(secret_share)?;
+        let mut signer = Signer::new(key_package, message.clone(), &mut rng)?;
+
+        let replies = if is_malicious {
+            let mut adversary = adversary.clone();
+            let replies = adversary.start(&mut signer, &mut rng);
+            adversaries.insert(identifier, adversary);
+            replies
+        } else {
+            Honest.start(&mut signer, &mut rng)
+        };
+
+        for reply in replies {
+            network.send(identifier, Payload::ToCoordinator(reply), &mut rng);
+        }
+        signers.insert(identifier, signer);
+    }
+
+    loop {
+        let Message {
+            identifier,
+            payload,
+            ..
+        } = network
+            .next(&adversaries, &mut rng)
+            .unwrap_or_else(|| panic!("simulation with seed {seed} stalled"));
+
+        match payload {
+            Payload::ToCoordinator((signature_share, signing_commitments)) => {
+                match coordinator.receive(identifier, signature_share, signing_commitments) {
+                    Ok(SessionStatus::InProgress) => {}
+                    Ok(SessionStatus::Started {
+                        signers,
+                        signing_package,
+                    }) => {
+                        for identifier in signers {
+                            network.send(
+                                identifier,
+                                Payload::ToSigner(signing_package.clone()),
+                                &mut rng,
+                            );
+                        }
+                    }
+                    Ok(SessionStatus::Finished { signature }) => {
+                        public_key_package
+                            .verifying_key()
+                            .verify(&message, &signature)?;
+                        break;
+                    }
+                    Err(Error::MaliciousSigner(_)) if adversaries.contains_key(&identifier) => {}
+                    Err(err) => return Err(err),
+                }
+            }
+            Payload::ToSigner(signing_package) => {
+                let signer = signers.get_mut(&identifier).unwrap();
+                let replies = match adversaries.get_mut(&identifier) {
+                    Some(adversary) => adversary.receive(signer, &signing_package, &mut rng),
+                    None => Honest.receive(signer, &signing_package, &mut rng),
+                };
+                for reply in replies {
+                    network.send(identifier, Payload::ToCoordinator(reply), &mut rng);
+                }
+            }
+        }
+    }
+
+    Ok(coordinator.session_count())
+}
+
+/// Runs [`simulate`] for every seed in `seeds` and checks that each run
+/// produces a valid signature within `malicious_signers + 1` sessions.
+///
+/// Panics with the failing seed, so the run can be replayed with [`simulate`].
+pub fn test_simulation<C: Ciphersuite, A: Adversary<C> + Clone>(
+    min_signers: u16,
+    max_signers: u16,
+    malicious_signers: u16,
+    adversary: A,
+    delivery_order: DeliveryOrder,
+    seeds: core::ops::Range<u64>,
+) {
+    for seed in seeds {
+        match simulate(
+            min_signers,
+            max_signers,
+            malicious_signers,
+            adversary.clone(),
+            delivery_order,
+            seed,
+        ) {
+            Ok(session_count) => assert!(
+                session_count <= malicious_signers + 1,
+                "simulation with seed {seed} started {session_count} sessions"
+            ),
+            Err(err) => panic!("simulation with seed {seed} failed: {err}"),
+        }
+    }
+}
@@ -0,0 +1,450 @@
+use crate::{Coordinator, Envelope, Error, Metrics, RunId, SessionStatus, SignedEnvelope};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use frost_core::{
+    keys::{PublicKeyPackage, VerifyingShare},
+    round1::SigningCommitments,
+    round2::SignatureShare,
+    Ciphersuite, Identifier, Signature, SigningPackage, VerifyingKey,
+};
+
+/// Represents recorded input of [`Coordinator`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "C: Ciphersuite"))]
+pub enum TranscriptInput<C: Ciphersuite> {
+    /// Input of [`Coordinator::receive`].
+    Receive {
+        /// Identifier of the signer.
+        identifier: Identifier<C>,
+        /// Signature share of the signer.
+        signature_share: Option<SignatureShare<C>>,
+        /// Signing commitments of the signer.
+        signing_commitments: SigningCommitments<C>,
+    },
+    /// Input of [`Coordinator::preprocess`].
+    Preprocess {
+        /// Identifier of the signer.
+        identifier: Identifier<C>,
+        /// Preprocessed signing commitments of the signer.
+        signing_commitments: Vec<SigningCommitments<C>>,
+    },
+    /// Input of [`Coordinator::receive_envelope`].
+    ReceiveEnvelope {
+        /// Reply of the signer.
+        envelope: Envelope<C>,
+    },
+    /// Input of [`Coordinator::receive_signed_envelope`].
+    ReceiveSignedEnvelope {
+        /// Signed reply of the signer.
+        signed_envelope: SignedEnvelope<C>,
+    },
+    /// Input of [`Coordinator::receive_batch`].
+    ReceiveBatch {
+        /// Replies of signers in order.
+        replies: Vec<(
+            Identifier<C>,
+            Option<SignatureShare<C>>,
+            SigningCommitments<C>,
+        )>,
+    },
+}
+
+/// Represents recorded output of [`Coordinator`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "C: Ciphersuite"))]
+pub enum TranscriptOutput<C: Ciphersuite> {
+    /// [`Coordinator::preprocess`] succeeded.
+    Preprocessed,
+    /// [`Coordinator::receive`] returned [`SessionStatus::InProgress`].
+    InProgress,
+    /// [`Coordinator::receive`] returned [`SessionStatus::Started`].
+    Started {
+        /// Set of signers with which session started.
+        signers: BTreeSet<Identifier<C>>,
+        /// Signing package of the session.
+        signing_package: SigningPackage<C>,
+    },
+    /// [`Coordinator::receive`] returned [`SessionStatus::Finished`].
+    Finished {
+        /// Final signature.
+        signature: Signature<C>,
+    },
+    /// [`Coordinator`] returned an error.
+    Error(Error<C>),
+    /// [`Coordinator::receive_batch`] returned outputs for each reply in
+    /// order.
+    Batch(Vec<TranscriptOutput<C>>),
+}
+
+impl<C: Ciphersuite> From<&Result<SessionStatus<C>, Error<C>>> for TranscriptOutput<C> {
+    fn from(result: &Result<SessionStatus<C>, Error<C>>) -> Self {
+        match result {
+            Ok(SessionStatus::InProgress) => Self::InProgress,
+            Ok(SessionStatus::Started {
+                signers,
+                signing_package,
+            }) => Self::Started {
+                signers: signers.clone(),
+                signing_package: signing_package.clone(),
+            },
+            Ok(SessionStatus::Finished { signature }) => Self::Finished {
+                signature: *signature,
+            },
+            Err(err) => Self::Error(*err),
+        }
+    }
+}
+
+impl<C: Ciphersuite> From<&Result<(), Error<C>>> for TranscriptOutput<C> {
+    fn from(result: &Result<(), Error<C>>) -> Self {
+        match result {
+            Ok(()) => Self::Preprocessed,
+            Err(err) => Self::Error(*err),
+        }
+    }
+}
+
+/// Represents recorded call of [`Coordinator`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "C: Ciphersuite"))]
+pub struct TranscriptEntry<C: Ciphersuite> {
+    /// Input of the call.
+    pub input: TranscriptInput<C>,
+    /// Output of the call.
+    pub output: TranscriptOutput<C>,
+}
+
+/// Represents transcript of [`Coordinator`] run.
+///
+/// Contains parameters of [`Coordinator`] (including its run id and identity
+/// keys of signers) and every call that receives replies or
+/// [`SigningCommitments`] of signers with its output. The transcript can be
+/// replayed with [`Transcript::replay`] to reproduce the run.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "C: Ciphersuite"))]
+pub struct Transcript<C: Ciphersuite> {
+    max_signers: u16,
+    min_signers: u16,
+    public_key_package: PublicKeyPackage<C>,
+    message: Vec<u8>,
+    run_id: RunId,
+    identity_keys: BTreeMap<Identifier<C>, VerifyingKey<C>>,
+    entries: Vec<TranscriptEntry<C>>,
+}
+
+impl<C: Ciphersuite> Transcript<C> {
+    /// Returns the maximum number of signers.
+    pub fn max_signers(&self) -> u16 {
+        self.max_signers
+    }
+
+    /// Returns the threshold number of signers.
+    pub fn min_signers(&self) -> u16 {
+        self.min_signers
+    }
+
+    /// Returns the [`PublicKeyPackage`] of the group.
+    pub fn public_key_package(&self) -> &PublicKeyPackage<C> {
+        &self.public_key_package
+    }
+
+    /// Returns the message to sign.
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+
+    /// Returns identifier of coordinator run.
+    pub fn run_id(&self) -> RunId {
+        self.run_id
+    }
+
+    /// Returns long-term identity keys of signers.
+    pub fn identity_keys(&self) -> &BTreeMap<Identifier<C>, VerifyingKey<C>> {
+        &self.identity_keys
+    }
+
+    /// Returns the recorded calls.
+    pub fn entries(&self) -> &[TranscriptEntry<C>] {
+        &self.entries
+    }
+
+    /// Feeds recorded inputs into a fresh [`Coordinator`] and checks that it
+    /// produces identical outputs.
+    ///
+    /// Returns the [`Coordinator`] in the state after the last entry if
+    /// successful. Returns [`Error::TranscriptMismatch`] with the index of the
+    /// first entry whose output differs.
+    ///
+    /// Replies of [`TranscriptInput::ReceiveBatch`] are replayed one by one,
+    /// since [`Coordinator::receive_batch`] produces the same outputs as
+    /// sequential processing.
+    pub fn replay(&self) -> Result<Coordinator<C>, Error<C>> {
+        let mut coordinator = Coordinator::new(
+            self.max_signers,
+            self.min_signers,
+            self.public_key_package.clone(),
+            self.message.clone(),
+        )?
+        .with_run_id(self.run_id)
+        .with_identity_keys(self.identity_keys.clone());
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            let output = match entry.input.clone() {
+                TranscriptInput::Receive {
+                    identifier,
+                    signature_share,
+                    signing_commitments,
+                } => TranscriptOutput::from(&coordinator.receive(
+                    identifier,
+                    signature_share,
+                    signing_commitments,
+                )),
+                TranscriptInput::Preprocess {
+                    identifier,
+                    signing_commitments,
+                } => {
+                    TranscriptOutput::from(&coordinator.preprocess(identifier, signing_commitments))
+                }
+                TranscriptInput::ReceiveEnvelope { envelope } => {
+                    TranscriptOutput::from(&coordinator.receive_envelope(envelope))
+                }
+                TranscriptInput::ReceiveSignedEnvelope { signed_envelope } => {
+                    TranscriptOutput::from(&coordinator.receive_signed_envelope(signed_envelope))
+                }
+                TranscriptInput::ReceiveBatch { replies } => TranscriptOutput::Batch(
+                    replies
+                        .into_iter()
+                        .map(|(identifier, signature_share, signing_commitments)| {
+                            TranscriptOutput::from(&coordinator.receive(
+                                identifier,
+                                signature_share,
+                                signing_commitments,
+                            ))
+                        })
+                        .collect(),
+                ),
+            };
+
+            if output != entry.output {
+                return Err(Error::TranscriptMismatch(index));
+            }
+        }
+
+        Ok(coordinator)
+    }
+}
+
+/// Represents coordinator that records [`Transcript`] of its run.
+#[derive(Debug)]
+pub struct TranscriptRecorder<C: Ciphersuite, M: Metrics = ()> {
+    coordinator: Coordinator<C, M>,
+    transcript: Transcript<C>,
+}
+
+impl<C: Ciphersuite> TranscriptRecorder<C> {
+    /// Creates a new [`TranscriptRecorder`] with a fresh [`Coordinator`].
+    pub fn new(
+        max_signers: u16,
+        min_signers: u16,
+        public_key_package: PublicKeyPackage<C>,
+        message: Vec<u8>,
+    ) -> Result<Self, Error<C>> {
+        Self::with_metrics(max_signers, min_signers, public_key_package, message, ())
+    }
+}
+
+impl<C: Ciphersuite, M: Metrics> TranscriptRecorder<C, M> {
+    /// Creates a new [`TranscriptRecorder`] with a fresh [`Coordinator`] that
+    /// reports metrics of the run to `metrics`.
+    pub fn with_metrics(
+        max_signers: u16,
+        min_signers: u16,
+        public_key_package: PublicKeyPackage<C>,
+        message: Vec<u8>,
+        metrics: M,
+    ) -> Result<Self, Error<C>> {
+        let coordinator = Coordinator::with_metrics(
+            max_signers,
+            min_signers,
+            public_key_package.clone(),
+            message.clone(),
+            metrics,
+        )?;
+        let transcript = Transcript {
+            max_signers,
+            min_signers,
+            public_key_package,
+            message,
+            run_id: coordinator.run_id(),
+            identity_keys: BTreeMap::new(),
+            entries: Vec::new(),
+        };
+
+        Ok(Self {
+            coordinator,
+            transcript,
+        })
+    }
+
+    /// Same as [`Coordinator::with_run_id`], but also records run id.
+    pub fn with_run_id(mut self, run_id: RunId) -> Self {
+        self.coordinator = self.coordinator.with_run_id(run_id);
+        self.transcript.run_id = run_id;
+        self
+    }
+
+    /// Same as [`Coordinator::with_identity_keys`], but also records identity
+    /// keys.
+    pub fn with_identity_keys(
+        mut self,
+        identity_keys: BTreeMap<Identifier<C>, VerifyingKey<C>>,
+    ) -> Self {
+        self.coordinator = self.coordinator.with_identity_keys(identity_keys.clone());
+        self.transcript.identity_keys = identity_keys;
+        self
+    }
+
+    /// Returns the underlying [`Coordinator`].
+    pub fn coordinator(&self) -> &Coordinator<C, M> {
+        &self.coordinator
+    }
+
+    /// Returns the recorded [`Transcript`].
+    pub fn transcript(&self) -> &Transcript<C> {
+        &self.transcript
+    }
+
+    /// Consumes the recorder and returns the recorded [`Transcript`].
+    pub fn into_transcript(self) -> Transcript<C> {
+        self.transcript
+    }
+
+    /// Same as [`Coordinator::receive`], but also records the call.
+    pub fn receive(
+        &mut self,
+        identifier: Identifier<C>,
+        signature_share: Option<SignatureShare<C>>,
+        signing_commitments: SigningCommitments<C>,
+    ) -> Result<SessionStatus<C>, Error<C>> {
+        let result = self
+            .coordinator
+            .receive(identifier, signature_share, signing_commitments);
+
+        self.transcript.entries.push(TranscriptEntry {
+            input: TranscriptInput::Receive {
+                identifier,
+                signature_share,
+                signing_commitments,
+            },
+            output: TranscriptOutput::from(&result),
+        });
+
+        result
+    }
+
+    /// Same as [`Coordinator::preprocess`], but also records the call.
+    pub fn preprocess(
+        &mut self,
+        identifier: Identifier<C>,
+        signing_commitments: Vec<SigningCommitments<C>>,
+    ) -> Result<(), Error<C>> {
+        let result = self
+            .coordinator
+            .preprocess(identifier, signing_commitments.clone());
+
+        self.transcript.entries.push(TranscriptEntry {
+            input: TranscriptInput::Preprocess {
+                identifier,
+                signing_commitments,
+            },
+            output: TranscriptOutput::from(&result),
+        });
+
+        result
+    }
+
+    /// Same as [`Coordinator::receive_envelope`], but also records the call.
+    pub fn receive_envelope(
+        &mut self,
+        envelope: Envelope<C>,
+    ) -> Result<SessionStatus<C>, Error<C>> {
+        let result = self.coordinator.receive_envelope(envelope.clone());
+
+        self.transcript.entries.push(TranscriptEntry {
+            input: TranscriptInput::ReceiveEnvelope { envelope },
+            output: TranscriptOutput::from(&result),
+        });
+
+        result
+    }
+
+    /// Same as [`Coordinator::receive_signed_envelope`], but also records the
+    /// call.
+    pub fn receive_signed_envelope(
+        &mut self,
+        signed_envelope: SignedEnvelope<C>,
+    ) -> Result<SessionStatus<C>, Error<C>> {
+        let result = self
+            .coordinator
+            .receive_signed_envelope(signed_envelope.clone());
+
+        self.transcript.entries.push(TranscriptEntry {
+            input: TranscriptInput::ReceiveSignedEnvelope { signed_envelope },
+            output: TranscriptOutput::from(&result),
+        });
+
+        result
+    }
+
+    /// Same as [`Coordinator::receive_batch`], but also records the call.
+    pub fn receive_batch(
+        &mut self,
+        replies: Vec<(
+            Identifier<C>,
+            Option<SignatureShare<C>>,
+            SigningCommitments<C>,
+        )>,
+    ) -> Vec<Result<SessionStatus<C>, Error<C>>>
+    where
+        Identifier<C>: Sync,
+        SignatureShare<C>: Sync,
+        SigningPackage<C>: Sync,
+        VerifyingShare<C>: Sync,
+        VerifyingKey<C>: Sync,
+    {
+        let results = self.coordinator.receive_batch(replies.clone());
+
+        self.transcript.entries.push(TranscriptEntry {
+            input: TranscriptInput::ReceiveBatch { replies },
+            output: TranscriptOutput::Batch(results.iter().map(TranscriptOutput::from).collect()),
+        });
+
+        results
+    }
+}
+
+#[cfg(feature = "serialization")]
+impl<C: Ciphersuite> Transcript<C> {
+    /// Serializes [`Transcript`] to bytes in the default serialization format.
+    ///
+    /// Returns [`Error::InvalidTranscript`] if an entry contains an error of
+    /// FROST protocol that [`Coordinator`] does not return.
+    pub fn serialize(&self) -> Result<Vec<u8>, Error<C>> {
+        postcard::to_allocvec(self).map_err(|_| Error::InvalidTranscript)
+    }
+
+    /// Deserializes [`Transcript`] from bytes produced by
+    /// [`Transcript::serialize`].
+    pub fn deserialize(bytes: &[u8]) -> Result<Self, Error<C>> {
+        match postcard::take_from_bytes(bytes) {
+            Ok((transcript, [])) => Ok(transcript),
+            _ => Err(Error::InvalidTranscript),
+        }
+    }
+}
@@ -0,0 +1,223 @@
+#![cfg(feature = "noise")]
+
+use frost_ristretto255::{
+    keys::{self, IdentifierList, KeyPackage},
+    Identifier, Ristretto255Sha512, SigningKey, VerifyingKey,
+};
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use roast_core::{ChannelError, Payload, SecureChannel, SessionStatus, StaticKeypair};
+use std::{
+    collections::BTreeMap,
+    net::{TcpListener, TcpStream},
+    sync::mpsc,
+    thread,
+};
+
+type Coordinator = roast_core::Coordinator<Ristretto255Sha512>;
+type Envelope = roast_core::Envelope<Ristretto255Sha512>;
+type SignedEnvelope = roast_core::SignedEnvelope<Ristretto255Sha512>;
+type Signer = roast_core::Signer<Ristretto255Sha512>;
+
+const MIN_SIGNERS: u16 = 3;
+const MAX_SIGNERS: u16 = 5;
+
+/// Runs signer that replies to every signing package until the coordinator
+/// closes the channel.
+///
+/// If `impersonated` is set, the signer first sends a reply on behalf of
+/// another signer.
+fn run_signer(
+    mut signer: Signer,
+    mut rng: ChaCha20Rng,
+    identity_key: SigningKey,
+    keypair: StaticKeypair,
+    coordinator_public_key: [u8; 32],
+    address: std::net::SocketAddr,
+    impersonated: Option<Identifier>,
+) -> Result<(), ChannelError> {
+    let stream = TcpStream::connect(address)?;
+    let mut channel = SecureChannel::initiate(stream, &keypair, coordinator_public_key)?;
+
+    let envelope = signer.envelope();
+    if let Some(identifier) = impersonated {
+        let Payload::Reply {
+            signature_share,
+            signing_commitments,
+            ..
+        } = envelope.payload().clone()
+        else {
+            unreachable!()
+        };
+        let forged = Envelope::new(
+            envelope.run_id(),
+            envelope.session_id(),
+            Payload::Reply {
+                identifier,
+                signature_share,
+                signing_commitments,
+            },
+        );
+        let forged = SignedEnvelope::sign(forged, &identity_key, &mut rng).unwrap();
+        channel.send_signed_envelope(&forged)?;
+    }
+    let envelope = SignedEnvelope::sign(envelope, &identity_key, &mut rng).unwrap();
+    channel.send_signed_envelope(&envelope)?;
+
+    loop {
+        let envelope: Envelope = match channel.receive_envelope() {
+            Ok(envelope) => envelope,
+            Err(ChannelError::Io(_)) => return Ok(()),
+            Err(err) => return Err(err),
+        };
+        let reply = signer
+            .receive_envelope(&envelope, &mut rng)
+            .expect("coordinator is honest");
+        let reply = SignedEnvelope::sign(reply, &identity_key, &mut rng).unwrap();
+        channel.send_signed_envelope(&reply)?;
+    }
+}
+
+#[test]
+fn roast_over_localhost() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let (secret_shares, public_key_package) =
+        keys::generate_with_dealer(MAX_SIGNERS, MIN_SIGNERS, IdentifierList::Default, &mut rng)
+            .unwrap();
+
+    let message = b"message to sign".to_vec();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let coordinator_keypair = StaticKeypair::generate().unwrap();
+
+    let mut identifiers: BTreeMap<[u8; 32], Identifier> = BTreeMap::new();
+    let mut identity_keys: BTreeMap<Identifier, VerifyingKey> = BTreeMap::new();
+    let mut signer_threads = Vec::new();
+    let all_identifiers: Vec<_> = secret_shares.keys().copied().collect();
+    for (identifier, secret_share) in secret_shares {
+        let key_package = KeyPackage::try_from(secret_share).unwrap();
+        let signer = Signer::new(key_package, message.clone(), &mut rng).unwrap();
+        let identity_key = SigningKey::new(&mut rng);
+        identity_keys.insert(identifier, VerifyingKey::from(&identity_key));
+        let keypair = StaticKeypair::generate().unwrap();
+        identifiers.insert(keypair.public_key(), identifier);
+
+        // The first signer tries to send a reply on behalf of the last one.
+        let impersonated =
+            (identifier == all_identifiers[0]).then(|| all_identifiers[all_identifiers.len() - 1]);
+        let signer_rng = ChaCha20Rng::seed_from_u64(identifiers.len() as u64);
+        let coordinator_public_key = coordinator_keypair.public_key();
+        signer_threads.push(thread::spawn(move || {
+            run_signer(
+                signer,
+                signer_rng,
+                identity_key,
+                keypair,
+                coordinator_public_key,
+                address,
+                impersonated,
+            )
+        }));
+    }
+
+    let mut coordinator = Coordinator::new(
+        MAX_SIGNERS,
+        MIN_SIGNERS,
+        public_key_package.clone(),
+        message.clone(),
+    )
+    .unwrap()
+    .with_identity_keys(identity_keys);
+
+    // Attacker without registered static key cannot connect.
+    let attacker = thread::spawn({
+        let coordinator_public_key = coordinator_keypair.public_key();
+        move || {
+            let stream = TcpStream::connect(address)?;
+            let keypair = StaticKeypair::generate()?;
+            SecureChannel::initiate(stream, &keypair, coordinator_public_key).map(|_| ())
+        }
+    });
+
+    let public_keys: Vec<_> = identifiers.keys().copied().collect();
+    let (replies_sender, replies) = mpsc::channel();
+    let (mismatches_sender, mismatches) = mpsc::channel();
+    let mut packages: BTreeMap<Identifier, mpsc::Sender<Envelope>> = BTreeMap::new();
+    let mut connection_threads = Vec::new();
+
+    let mut attacker_rejected = false;
+
+    while packages.len() < MAX_SIGNERS as usize || !attacker_rejected {
+        let (stream, _) = listener.accept().unwrap();
+        let mut channel = match SecureChannel::respond(stream, &coordinator_keypair, &public_keys) {
+            Ok(channel) => channel,
+            Err(ChannelError::UnknownPeer) => {
+                attacker_rejected = true;
+                continue;
+            }
+            Err(err) => panic!("{err}"),
+        };
+        let identifier = identifiers[&channel.remote_public_key()];
+
+        let (package_sender, package_receiver) = mpsc::channel::<Envelope>();
+        packages.insert(identifier, package_sender);
+        let replies_sender = replies_sender.clone();
+        let mismatches_sender = mismatches_sender.clone();
+        let identifiers = identifiers.clone();
+
+        connection_threads.push(thread::spawn(move || loop {
+            // Signers can only send messages on their own behalf.
+            let signed_envelope = match channel.receive_signed_reply(&identifiers) {
+                Ok(signed_envelope) => signed_envelope,
+                Err(ChannelError::SenderMismatch) => {
+                    mismatches_sender.send(identifier).unwrap();
+                    continue;
+                }
+                Err(_) => return,
+            };
+            if replies_sender.send(signed_envelope).is_err() {
+                return;
+            }
+            let Ok(package) = package_receiver.recv() else {
+                return;
+            };
+            if channel.send_envelope(&package).is_err() {
+                return;
+            }
+        }));
+    }
+    assert!(attacker.join().unwrap().is_err());
+
+    let signature = loop {
+        let signed_envelope = replies.recv().unwrap();
+        match coordinator
+            .receive_signed_envelope(signed_envelope)
+            .unwrap()
+        {
+            SessionStatus::InProgress => {}
+            SessionStatus::Started { signers, .. } => {
+                let package = coordinator.envelope(coordinator.session_count()).unwrap();
+                for identifier in signers {
+                    packages[&identifier].send(package.clone()).unwrap();
+                }
+            }
+            SessionStatus::Finished { signature } => break signature,
+        }
+    };
+
+    public_key_package
+        .verifying_key()
+        .verify(&message, &signature)
+        .unwrap();
+
+    drop(packages);
+    drop(replies);
+    drop(mismatches_sender);
+    for connection_thread in connection_threads {
+        connection_thread.join().unwrap();
+    }
+    assert_eq!(mismatches.iter().collect::<Vec<_>>(), [all_identifiers[0]]);
+    for signer_thread in signer_threads {
+        signer_thread.join().unwrap().unwrap();
+    }
+}
@@ -0,0 +1,249 @@
+use frost_ristretto255::{
+    keys::{self, IdentifierList, KeyPackage},
+    round1::SigningCommitments,
+    round2::SignatureShare,
+    Identifier, Ristretto255Sha512, SigningPackage,
+};
+use proptest::prelude::*;
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use roast_core::{Error, SessionStatus};
+use std::collections::{BTreeMap, BTreeSet};
+
+type Coordinator = roast_core::Coordinator<Ristretto255Sha512>;
+type Signer = roast_core::Signer<Ristretto255Sha512>;
+type Reply = (Option<SignatureShare>, SigningCommitments);
+
+/// Represents misbehaviour of malicious signer.
+#[derive(Debug, Clone, Copy)]
+enum Misbehaviour {
+    /// Sends fresh signing commitments without signature share.
+    FreshCommitments,
+    /// Sends valid signature share to the last received signing package.
+    ValidShare,
+    /// Sends zero signature share.
+    GarbageShare,
+    /// Sends signing commitments it has already sent.
+    ReplayCommitments,
+    /// Sends signing commitments of honest signer, which are possibly still
+    /// in flight.
+    CopyCommitments,
+}
+
+/// Represents a single step of the run.
+#[derive(Debug, Clone, Copy)]
+enum Action {
+    /// Delivers pending reply of honest signer.
+    Deliver(usize),
+    /// Malicious signer sends reply.
+    Misbehave(usize, Misbehaviour),
+}
+
+fn action() -> impl Strategy<Value = Action> {
+    let misbehaviour = prop_oneof![
+        Just(Misbehaviour::FreshCommitments),
+        Just(Misbehaviour::ValidShare),
+        Just(Misbehaviour::GarbageShare),
+        Just(Misbehaviour::ReplayCommitments),
+        Just(Misbehaviour::CopyCommitments),
+    ];
+
+    prop_oneof![
+        3 => any::<usize>().prop_map(Action::Deliver),
+        1 => (any::<usize>(), misbehaviour)
+            .prop_map(|(index, misbehaviour)| Action::Misbehave(index, misbehaviour)),
+    ]
+}
+
+fn parameters() -> impl Strategy<Value = (u16, u16, u16)> {
+    (2u16..=5, 0u16..=3).prop_flat_map(|(min_signers, extra_signers)| {
+        let max_signers = min_signers + extra_signers;
+        (
+            Just(min_signers),
+            Just(max_signers),
+            0..=max_signers - min_signers,
+        )
+    })
+}
+
+struct Malicious {
+    signer: Signer,
+    signing_package: Option<SigningPackage>,
+    sent: Vec<SigningCommitments>,
+}
+
+fn run(
+    (min_signers, max_signers, malicious_signers): (u16, u16, u16),
+    actions: Vec<Action>,
+    seed: u64,
+) -> Result<(), TestCaseError> {
+    let mut rng = ChaCha20Rng::seed_from_u64(seed);
+
+    let (secret_shares, public_key_package) =
+        keys::generate_with_dealer(max_signers, min_signers, IdentifierList::Default, &mut rng)
+            .unwrap();
+
+    let message = b"message to sign".to_vec();
+    let mut coordinator = Coordinator::new(
+        max_signers,
+        min_signers,
+        public_key_package.clone(),
+        message.clone(),
+    )
+    .unwrap();
+
+    let mut honest: BTreeMap<Identifier, Signer> = BTreeMap::new();
+    let mut malicious: Vec<(Identifier, Malicious)> = Vec::new();
+    let mut pending: Vec<(Identifier, Reply)> = Vec::new();
+    let mut honest_signing_commitments: Vec<SigningCommitments> = Vec::new();
+
+    for (index, (identifier, secret_share)) in secret_shares.into_iter().enumerate() {
+        let key_package = KeyPackage::try_from(secret_share).unwrap();
+        let signer = Signer::new(key_package, message.clone(), &mut rng).unwrap();
+
+        if index < malicious_signers as usize {
+            malicious.push((
+                identifier,
+                Malicious {
+                    signer,
+                    signing_package: None,
+                    sent: Vec::new(),
+                },
+            ));
+        } else {
+            pending.push((identifier, (None, signer.signing_commitments())));
+            honest_signing_commitments.push(signer.signing_commitments());
+            honest.insert(identifier, signer);
+        }
+    }
+
+    for action in actions {
+        let (identifier, (signature_share, signing_commitments)) = match action {
+            Action::Deliver(index) => {
+                if pending.is_empty() {
+                    continue;
+                }
+                pending.swap_remove(index % pending.len())
+            }
+            Action::Misbehave(index, misbehaviour) => {
+                if malicious.is_empty() {
+                    continue;
+                }
+                let len = malicious.len();
+                let (identifier, malicious) = &mut malicious[index % len];
+
+                let reply = match misbehaviour {
+                    Misbehaviour::FreshCommitments => {
+                        let signing_commitments = malicious.signer.preprocess(1, &mut rng).unwrap();
+                        (None, signing_commitments[0])
+                    }
+                    Misbehaviour::ValidShare => {
+                        let Some(signing_package) = malicious.signing_package.take() else {
+                            continue;
+                        };
+                        let Ok(signature_share) =
+                            malicious.signer.receive(&signing_package, &mut rng)
+                        else {
+                            continue;
+                        };
+                        (
+                            Some(signature_share),
+                            malicious.signer.signing_commitments(),
+                        )
+                    }
+                    Misbehaviour::GarbageShare => {
+                        let signature_share = SignatureShare::deserialize(&[0; 32]).unwrap();
+                        let signing_commitments = malicious.signer.preprocess(1, &mut rng).unwrap();
+                        (Some(signature_share), signing_commitments[0])
+                    }
+                    Misbehaviour::ReplayCommitments => {
+                        let Some(signing_commitments) = malicious.sent.last().copied() else {
+                            continue;
+                        };
+                        (None, signing_commitments)
+                    }
+                    Misbehaviour::CopyCommitments => {
+                        if honest_signing_commitments.is_empty() {
+                            continue;
+                        }
+                        let signing_commitments =
+                            honest_signing_commitments[index % honest_signing_commitments.len()];
+                        (None, signing_commitments)
+                    }
+                };
+
+                malicious.sent.push(reply.1);
+                (*identifier, reply)
+            }
+        };
+
+        let result = coordinator.receive(identifier, signature_share, signing_commitments);
+
+        if honest.contains_key(&identifier) {
+            prop_assert!(
+                result.is_ok(),
+                "honest signer got {:?}",
+                result.as_ref().err()
+            );
+        }
+
+        match result {
+            Ok(SessionStatus::InProgress) => {}
+            Ok(SessionStatus::Started {
+                signers,
+                signing_package,
+            }) => {
+                for identifier in signers {
+                    if let Some(signer) = honest.get_mut(&identifier) {
+                        let signature_share = signer.receive(&signing_package, &mut rng).unwrap();
+                        honest_signing_commitments.push(signer.signing_commitments());
+                        pending.push((
+                            identifier,
+                            (Some(signature_share), signer.signing_commitments()),
+                        ));
+                    } else if let Some((_, malicious)) =
+                        malicious.iter_mut().find(|(id, _)| *id == identifier)
+                    {
+                        malicious.signing_package = Some(signing_package.clone());
+                    }
+                }
+            }
+            Ok(SessionStatus::Finished { signature }) => {
+                prop_assert!(public_key_package
+                    .verifying_key()
+                    .verify(&message, &signature)
+                    .is_ok());
+                break;
+            }
+            Err(Error::MaliciousSigner(_)) => {}
+            Err(err) => prop_assert!(false, "unexpected error {err:?}"),
+        }
+
+        for identifier in coordinator.malicious_signers().keys() {
+            prop_assert!(!honest.contains_key(identifier));
+        }
+
+        let mut waiting_signers = BTreeSet::new();
+        for session in coordinator.open_sessions() {
+            for identifier in session.missing_signers() {
+                prop_assert!(waiting_signers.insert(identifier));
+            }
+        }
+
+        prop_assert!(coordinator.session_count() <= max_signers - min_signers + 1);
+    }
+
+    Ok(())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn coordinator_invariants(
+        parameters in parameters(),
+        actions in proptest::collection::vec(action(), 0..128),
+        seed in any::<u64>(),
+    ) {
+        run(parameters, actions, seed)?;
+    }
+}
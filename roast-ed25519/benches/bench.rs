@@ -0,0 +1,10 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use roast_ed25519::frost::rand_core::OsRng;
+
+fn bench_ed25519(c: &mut Criterion) {
+    let mut rng = OsRng;
+    roast_core::benches::bench_roast::<frost_ed25519::Ed25519Sha512, _>(c, "ed25519", &mut rng);
+}
+
+criterion_group!(benches, bench_ed25519);
+criterion_main!(benches);
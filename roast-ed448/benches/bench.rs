@@ -0,0 +1,10 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use roast_ed448::frost::rand_core::OsRng;
+
+fn bench_ed448(c: &mut Criterion) {
+    let mut rng = OsRng;
+    roast_core::benches::bench_roast::<frost_ed448::Ed448Shake256, _>(c, "ed448", &mut rng);
+}
+
+criterion_group!(benches, bench_ed448);
+criterion_main!(benches);
@@ -1,18 +1,3 @@
-use roast_core::tests;
-use roast_ed448::{frost::rand_core::OsRng, Error};
+use roast_ed448::frost::{rand_core::OsRng, Ed448Shake256};
 
-#[test]
-fn test_basic() -> Result<(), Error> {
-    let mut rng = OsRng;
-    tests::test_basic(2, 3, &mut rng)?;
-    tests::test_basic(67, 100, &mut rng)?;
-    Ok(())
-}
-
-#[test]
-fn test_malicious() -> Result<(), Error> {
-    let mut rng = OsRng;
-    tests::test_malicious(2, 3, 1, &mut rng)?;
-    tests::test_malicious(67, 100, 33, &mut rng)?;
-    Ok(())
-}
+roast_core::integration_tests!(Ed448Shake256, OsRng);
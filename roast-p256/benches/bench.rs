@@ -0,0 +1,10 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use roast_p256::frost::rand_core::OsRng;
+
+fn bench_p256(c: &mut Criterion) {
+    let mut rng = OsRng;
+    roast_core::benches::bench_roast::<frost_p256::P256Sha256, _>(c, "p256", &mut rng);
+}
+
+criterion_group!(benches, bench_p256);
+criterion_main!(benches);
@@ -0,0 +1,14 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use roast_ristretto255::frost::rand_core::OsRng;
+
+fn bench_ristretto255(c: &mut Criterion) {
+    let mut rng = OsRng;
+    roast_core::benches::bench_roast::<frost_ristretto255::Ristretto255Sha512, _>(
+        c,
+        "ristretto255",
+        &mut rng,
+    );
+}
+
+criterion_group!(benches, bench_ristretto255);
+criterion_main!(benches);
@@ -4,27 +4,114 @@
 #![doc = document_features::document_features!()]
 
 mod coordinator {
+    pub use roast_core::{Metrics, SessionId, SignerStatus, TimeoutPolicy};
+
+    #[cfg(feature = "std")]
+    pub use roast_core::InMemoryMetrics;
+
     /// Represents all possible session statuses.
     pub type SessionStatus = roast_core::SessionStatus<frost_ristretto255::Ristretto255Sha512>;
 
+    /// Represents read-only view of session.
+    pub type SessionInfo<'a> = roast_core::SessionInfo<'a, frost_ristretto255::Ristretto255Sha512>;
+
+    /// Represents event that occurred while processing batch of replies.
+    pub type CoordinatorEvent =
+        roast_core::CoordinatorEvent<frost_ristretto255::Ristretto255Sha512>;
+
     /// Represents coordinator.
-    pub type Coordinator = roast_core::Coordinator<frost_ristretto255::Ristretto255Sha512>;
+    pub type Coordinator<M = ()> =
+        roast_core::Coordinator<frost_ristretto255::Ristretto255Sha512, M>;
+
+    /// Represents recorded input of coordinator.
+    pub type TranscriptInput = roast_core::TranscriptInput<frost_ristretto255::Ristretto255Sha512>;
+
+    /// Represents recorded output of coordinator.
+    pub type TranscriptOutput =
+        roast_core::TranscriptOutput<frost_ristretto255::Ristretto255Sha512>;
+
+    /// Represents recorded call of coordinator.
+    pub type TranscriptEntry = roast_core::TranscriptEntry<frost_ristretto255::Ristretto255Sha512>;
+
+    /// Represents transcript of coordinator run.
+    pub type Transcript = roast_core::Transcript<frost_ristretto255::Ristretto255Sha512>;
+
+    /// Represents coordinator that records transcript of its run.
+    pub type TranscriptRecorder<M = ()> =
+        roast_core::TranscriptRecorder<frost_ristretto255::Ristretto255Sha512, M>;
+
+    /// Represents signer that has not replied to signing package within
+    /// deadline.
+    pub type TimeoutReport = roast_core::TimeoutReport<frost_ristretto255::Ristretto255Sha512>;
+
+    /// Represents coordinator that tracks liveness of signers.
+    pub type LivenessCoordinator<M = ()> =
+        roast_core::LivenessCoordinator<frost_ristretto255::Ristretto255Sha512, M>;
+
+    /// Represents several coordinators that sign the same message in
+    /// parallel.
+    pub type MultiCoordinator<M = ()> =
+        roast_core::MultiCoordinator<frost_ristretto255::Ristretto255Sha512, M>;
 }
 
 mod error {
-    pub use roast_core::MaliciousSignerError;
+    pub use roast_core::{MaliciousCoordinatorError, MaliciousSignerError, NonceStoreError};
 
     /// Represents all possible errors that can occur.
     pub type Error = roast_core::Error<frost_ristretto255::Ristretto255Sha512>;
+
+    /// Represents error of batch processing with events of the whole batch.
+    pub type ReceiveManyError =
+        roast_core::ReceiveManyError<frost_ristretto255::Ristretto255Sha512>;
+
+    /// Represents error of liveness tracking with reports about all
+    /// unresponsive signers.
+    pub type TickError = roast_core::TickError<frost_ristretto255::Ristretto255Sha512>;
+}
+
+mod message {
+    pub use roast_core::{RunId, PROTOCOL_VERSION};
+
+    /// Represents all possible payloads exchanged between coordinator and
+    /// signers.
+    pub type Payload = roast_core::Payload<frost_ristretto255::Ristretto255Sha512>;
+
+    /// Represents versioned message.
+    pub type Envelope = roast_core::Envelope<frost_ristretto255::Ristretto255Sha512>;
+
+    /// Represents envelope signed with long-term identity key of its sender.
+    pub type SignedEnvelope = roast_core::SignedEnvelope<frost_ristretto255::Ristretto255Sha512>;
 }
 
 mod signer {
+    pub use roast_core::{NonceStore, SigningPackageDigest};
+
+    /// Represents in-memory nonce store.
+    pub type MemoryNonceStore =
+        roast_core::MemoryNonceStore<frost_ristretto255::Ristretto255Sha512>;
+
+    /// Represents file-backed nonce store.
+    #[cfg(all(feature = "std", feature = "serialization"))]
+    pub type FileNonceStore = roast_core::FileNonceStore<frost_ristretto255::Ristretto255Sha512>;
+
     /// Represents signer.
-    pub type Signer = roast_core::Signer<frost_ristretto255::Ristretto255Sha512>;
+    pub type Signer<S = MemoryNonceStore> =
+        roast_core::Signer<frost_ristretto255::Ristretto255Sha512, S>;
+
+    /// Represents signer that takes part in runs of several coordinators.
+    pub type MultiSigner<S = MemoryNonceStore> =
+        roast_core::MultiSigner<frost_ristretto255::Ristretto255Sha512, S>;
 }
 
 pub use frost_ristretto255 as frost;
 
+#[cfg(feature = "protobuf")]
+pub use roast_core::protobuf;
+
+#[cfg(feature = "noise")]
+pub use roast_core::{ChannelError, SecureChannel, StaticKeypair, MAX_CHANNEL_MESSAGE_LEN};
+
 pub use coordinator::*;
 pub use error::*;
+pub use message::*;
 pub use signer::*;
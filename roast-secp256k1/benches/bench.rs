@@ -0,0 +1,14 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use roast_secp256k1::frost::rand_core::OsRng;
+
+fn bench_secp256k1(c: &mut Criterion) {
+    let mut rng = OsRng;
+    roast_core::benches::bench_roast::<frost_secp256k1::Secp256K1Sha256, _>(
+        c,
+        "secp256k1",
+        &mut rng,
+    );
+}
+
+criterion_group!(benches, bench_secp256k1);
+criterion_main!(benches);